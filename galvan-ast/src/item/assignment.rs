@@ -15,9 +15,38 @@ pub struct Assignment {
 }
 
 #[type_union]
-#[derive(Debug, PartialEq, Eq, FromPest)]
-#[pest_ast(rule(Rule::assignment_target))]
-pub type AssignmentTarget = Ident + MemberChain;
+#[derive(Debug, PartialEq, Eq)]
+pub type AssignmentTarget = Ident + MemberChain + AccessExpression;
+
+impl FromPest<'_> for AssignmentTarget {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::assignment_target {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        if pairs.peek().ok_or(NoMatch)?.as_rule() == Rule::member_chain {
+            return Ok(MemberChain::from_pest(&mut pairs)?.into());
+        }
+
+        // Only variables and indexed elements can be assigned to
+        match SingleExpression::from_pest(&mut pairs)? {
+            SingleExpression::Ident(ident) => Ok(ident.into()),
+            SingleExpression::Postfix(postfix) => match *postfix {
+                PostfixExpression::AccessExpression(access) => Ok(access.into()),
+                _ => Err(NoMatch),
+            },
+            _ => Err(NoMatch),
+        }
+    }
+}
 
 #[type_union]
 #[derive(Debug, PartialEq, Eq)]
@@ -1,5 +1,7 @@
+use crate::item::type_item::ClosureArgumentType;
 use crate::{
-    Block, Body, Expression, FunctionCall, Ident, SingleExpression, TopExpression, TypeElement,
+    Block, Body, Expression, FunctionCall, Ident, SingleExpression, Statement, TopExpression,
+    TypeElement,
 };
 use from_pest::pest::iterators::Pairs;
 use from_pest::ConversionError::NoMatch;
@@ -46,19 +48,56 @@ impl FromPest<'_> for Closure {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, FromPest)]
-#[pest_ast(rule(Rule::closure_argument))]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ClosureArgument {
     pub ident: Ident,
     pub ty: Option<TypeElement>,
 }
 
+impl FromPest<'_> for ClosureArgument {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::closure_argument {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        let ident = Ident::from_pest(&mut pairs)?;
+        let ty = Option::<ClosureArgumentType>::from_pest(&mut pairs)?.map(Into::into);
+        Ok(Self { ident, ty })
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct ElseExpression {
     pub receiver: Box<SingleExpression>,
+    /// Binds the error of the receiver e.g.: `try x |value| { ... } else |error| { ... }`
+    pub error: Option<ClosureArgument>,
+    /// An `else if` chain is represented as a block that contains the next `if`
     pub block: Block,
 }
 
+impl ElseExpression {
+    /// Returns true if the else branch is an `if` without an else branch, so the expression might not have a value
+    pub fn is_open(&self) -> bool {
+        match self.block.body.statements.as_slice() {
+            [Statement::TopExpression(TopExpression::Expression(Expression::SingleExpression(
+                SingleExpression::FunctionCall(call),
+            )))] => call.identifier.as_str() == "if",
+            [Statement::TopExpression(TopExpression::ElseExpression(else_expression))] => {
+                else_expression.is_open()
+            }
+            _ => false,
+        }
+    }
+}
+
 impl FromPest<'_> for ElseExpression {
     type Rule = Rule;
     type FatalError = Void;
@@ -74,21 +113,44 @@ impl FromPest<'_> for ElseExpression {
         }
 
         let mut pairs = pair.into_inner();
-        // println!("Else-Expression: \n{:#?}", pairs);
         let receiver_pair = pairs.peek().ok_or(NoMatch)?;
+        let receiver = match receiver_pair.as_rule() {
+            Rule::single_expression => Box::new(SingleExpression::from_pest(&mut pairs)?),
+            Rule::trailing_closure_call => Box::new(FunctionCall::from_pest(&mut pairs)?.into()),
+            _ => return Err(NoMatch),
+        };
 
-        match receiver_pair.as_rule() {
-            Rule::single_expression => {
-                let receiver = Box::new(SingleExpression::from_pest(&mut pairs)?);
-                let block = Block::from_pest(&mut pairs)?;
-                Ok(Self { receiver, block })
-            }
-            Rule::trailing_closure_call => {
-                let receiver = Box::new(FunctionCall::from_pest(&mut pairs)?.into());
-                let block = Block::from_pest(&mut pairs)?;
-                Ok(Self { receiver, block })
-            }
-            _ => Err(NoMatch),
+        let branch_pair = pairs.peek().ok_or(NoMatch)?;
+        if branch_pair.as_rule() == Rule::else_if {
+            pairs.next();
+            let mut pairs = branch_pair.into_inner();
+            let next = match pairs.peek().ok_or(NoMatch)?.as_rule() {
+                Rule::else_expression => {
+                    TopExpression::from(ElseExpression::from_pest(&mut pairs)?)
+                }
+                Rule::trailing_closure_call => TopExpression::from(Expression::from(
+                    SingleExpression::from(FunctionCall::from_pest(&mut pairs)?),
+                )),
+                _ => return Err(NoMatch),
+            };
+            let block = Block {
+                body: Body {
+                    statements: vec![next.into()],
+                },
+            };
+            return Ok(Self {
+                receiver,
+                error: None,
+                block,
+            });
         }
+
+        let error = Option::<ClosureArgument>::from_pest(&mut pairs)?;
+        let block = Block::from_pest(&mut pairs)?;
+        Ok(Self {
+            receiver,
+            error,
+            block,
+        })
     }
 }
@@ -0,0 +1,45 @@
+use from_pest::pest::iterators::Pairs;
+use from_pest::ConversionError::NoMatch;
+use from_pest::{ConversionError, FromPest, Void};
+use galvan_pest::Rule;
+
+use super::{Expression, Ident, TypeElement, Visibility};
+
+/// Top-level constant, its value has to be known at compile time
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConstDecl {
+    pub visibility: Visibility,
+    pub identifier: Ident,
+    pub ty: TypeElement,
+    pub value: Expression,
+}
+
+impl FromPest<'_> for ConstDecl {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::const_decl {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        let visibility = Visibility::from_pest(&mut pairs)?;
+        // The const keyword only distinguishes constants from other top-level items
+        pairs.next();
+        let identifier = Ident::from_pest(&mut pairs)?;
+        let ty = TypeElement::from_pest(&mut pairs)?;
+        let value = Expression::from_pest(&mut pairs)?;
+
+        Ok(Self {
+            visibility,
+            identifier,
+            ty,
+            value,
+        })
+    }
+}
@@ -0,0 +1,67 @@
+use from_pest::pest::iterators::{Pair, Pairs};
+use from_pest::ConversionError::NoMatch;
+use from_pest::{ConversionError, FromPest, Void};
+use galvan_pest::Rule;
+
+use super::*;
+
+#[derive(Debug, PartialEq, Eq)]
+/// Returns early from the surrounding function e.g.: `return n`
+pub struct Return {
+    pub value: Option<Box<Expression>>,
+}
+
+impl FromPest<'_> for Return {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::return_expression {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let value = match pair.into_inner().next() {
+            Some(value) => Some(Box::new(exit_value(value)?)),
+            None => None,
+        };
+        Ok(Self { value })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+/// Returns an error early from a function that returns a result e.g.: `throw "Division by zero"`
+pub struct Throw {
+    pub value: Box<Expression>,
+}
+
+impl FromPest<'_> for Throw {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::throw_expression {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let value = pair.into_inner().next().ok_or(NoMatch)?;
+        Ok(Self {
+            value: Box::new(exit_value(value)?),
+        })
+    }
+}
+
+/// Converts the value that is passed to `break`, `return` or `throw`
+pub(crate) fn exit_value(pair: Pair<'_, Rule>) -> Result<Expression, ConversionError<Void>> {
+    if pair.as_rule() != Rule::exit_value {
+        return Err(NoMatch);
+    }
+    Expression::from_pest(&mut pair.into_inner())
+}
@@ -1,23 +1,117 @@
 use super::*;
 use derive_more::From;
+use from_pest::pest::iterators::Pairs;
+use from_pest::ConversionError::NoMatch;
+use from_pest::{ConversionError, FromPest, Void};
 use galvan_pest::Rule;
 
-#[derive(Debug, PartialEq, Eq, FromPest)]
-#[pest_ast(rule(Rule::function))]
+#[derive(Debug, PartialEq, Eq)]
 pub struct FnDecl {
+    pub annotations: Vec<Annotation>,
     pub signature: FnSignature,
     pub block: Body,
+    /// Set for functions defined with `=`, their return type is inferred if it is not declared
+    pub is_expression_body: bool,
+}
+
+impl FromPest<'_> for FnDecl {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::function {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        let annotations = Vec::<Annotation>::from_pest(&mut pairs)?;
+        let signature = FnSignature::from_pest(&mut pairs)?;
+        let pair = pairs.next().ok_or(NoMatch)?;
+        let (block, is_expression_body) = match pair.as_rule() {
+            Rule::body => (Body::from_pest(&mut Pairs::single(pair))?, false),
+            Rule::expression_body => {
+                let expression = TopExpression::from_pest(&mut pair.into_inner())?;
+                let block = Body {
+                    statements: vec![expression.into()],
+                };
+                (block, true)
+            }
+            _ => return Err(NoMatch),
+        };
+
+        Ok(FnDecl {
+            annotations,
+            signature,
+            block,
+            is_expression_body,
+        })
+    }
+}
+
+impl FnDecl {
+    pub fn annotation(&self, name: &str) -> Option<&Annotation> {
+        self.annotations
+            .iter()
+            .find(|annotation| annotation.identifier.as_str() == name)
+    }
+}
+
+/// Annotation of a declaration e.g.: `@infix("⨁")`
+#[derive(Debug, PartialEq, Eq)]
+pub struct Annotation {
+    pub identifier: Ident,
+    pub arguments: Vec<AnnotationArg>,
+}
+
+impl FromPest<'_> for Annotation {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::annotation {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        let identifier = Ident::from_pest(&mut pairs)?;
+        let arguments = match pairs.next() {
+            Some(params) => Vec::<AnnotationArg>::from_pest(&mut params.into_inner())?,
+            None => vec![],
+        };
+
+        Ok(Annotation {
+            identifier,
+            arguments,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, From, FromPest)]
+#[pest_ast(rule(Rule::annotation_arg))]
+pub enum AnnotationArg {
+    Ident(Ident),
+    TypeIdent(TypeIdent),
+    String(StringLiteral),
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, FromPest)]
+#[derive(Debug, PartialEq, Eq, FromPest)]
 #[pest_ast(rule(Rule::fn_signature))]
 pub struct FnSignature {
-    // pub asyncness: Async,
-    // pub constness: Const,
     pub visibility: Visibility,
+    pub constness: Const,
+    pub asyncness: Async,
     pub identifier: Ident,
     pub parameters: ParamList,
     pub return_type: Option<TypeElement>,
+    pub where_clause: Option<WhereClause>,
 }
 
 impl FnSignature {
@@ -26,15 +120,17 @@ impl FnSignature {
         ident: Ident,
         parameters: ParamList,
         return_type: Option<TypeElement>,
+        where_clause: Option<WhereClause>,
     ) -> Self {
         // TODO: Verify that only first parameter is named self (or no self exists)
         FnSignature {
-            // asyncness: mods.asyncness,
-            // constness: mods.constness,
             visibility: mods.visibility,
+            constness: mods.constness,
+            asyncness: mods.asyncness,
             identifier: ident,
             parameters,
             return_type,
+            where_clause,
         }
     }
 
@@ -44,23 +140,86 @@ impl FnSignature {
             .first()
             .filter(|param| param.identifier.as_str() == "self")
     }
+
+    /// Labels of the labeled parameters in order of declaration, they identify the function together with its name
+    pub fn labels(&self) -> Vec<&str> {
+        self.parameters
+            .params
+            .iter()
+            .filter_map(|param| param.label.as_ref().map(Ident::as_str))
+            .collect()
+    }
+
+    /// Type parameters used in the parameters and the return type in order of their first appearance
+    pub fn generics(&self) -> Vec<&TypeIdent> {
+        let mut generics = vec![];
+        for param in &self.parameters.params {
+            param.param_type.collect_generics(&mut generics);
+        }
+        if let Some(return_type) = &self.return_type {
+            return_type.collect_generics(&mut generics);
+        }
+        generics
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, FromPest)]
+#[derive(Debug, PartialEq, Eq, FromPest)]
 #[pest_ast(rule(Rule::param_list))]
 pub struct ParamList {
     pub params: Vec<Param>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, FromPest)]
-#[pest_ast(rule(Rule::param))]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Param {
     pub decl_modifier: Option<DeclModifier>,
+    /// Label that names the argument at the call site e.g.: `to` in `fn move(to target: Point)`
+    pub label: Option<Ident>,
     pub identifier: Ident,
     pub param_type: TypeElement,
+    /// Value that is passed when the argument is omitted
+    pub default_value: Option<Expression>,
+}
+
+impl FromPest<'_> for Param {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::param {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        let decl_modifier = Option::<DeclModifier>::from_pest(&mut pairs)?;
+        let label = match pairs.peek() {
+            Some(pair) if pair.as_rule() == Rule::param_label => {
+                pairs.next();
+                Some(Ident::from_pest(&mut pair.into_inner())?)
+            }
+            _ => None,
+        };
+        let identifier = Ident::from_pest(&mut pairs)?;
+        let param_type = TypeElement::from_pest(&mut pairs)?;
+        let default_value = match pairs.next() {
+            Some(pair) => Some(Expression::from_pest(&mut pair.into_inner())?),
+            None => None,
+        };
+
+        Ok(Self {
+            decl_modifier,
+            label,
+            identifier,
+            param_type,
+            default_value,
+        })
+    }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, From, FromPest)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, From, FromPest)]
 #[pest_ast(rule(Rule::declaration_modifier))]
 pub enum DeclModifier {
     Let(LetKeyword),
@@ -68,14 +227,14 @@ pub enum DeclModifier {
     Ref(RefKeyword),
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, FromPest)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, FromPest)]
 #[pest_ast(rule(Rule::let_keyword))]
 pub struct LetKeyword;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, FromPest)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, FromPest)]
 #[pest_ast(rule(Rule::mut_keyword))]
 pub struct MutKeyword;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, FromPest)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, FromPest)]
 #[pest_ast(rule(Rule::ref_keyword))]
 pub struct RefKeyword;
@@ -11,6 +11,16 @@ pub struct FunctionCall {
     pub arguments: Vec<FunctionCallArg>,
 }
 
+impl FunctionCall {
+    /// Labels of the labeled arguments in order of appearance
+    pub fn labels(&self) -> Vec<&str> {
+        self.arguments
+            .iter()
+            .filter_map(|arg| arg.label.as_ref().map(Ident::as_str))
+            .collect()
+    }
+}
+
 impl FromPest<'_> for FunctionCall {
     type Rule = Rule;
     type FatalError = Void;
@@ -27,25 +37,16 @@ impl FromPest<'_> for FunctionCall {
                 let mut pairs = pair.into_inner();
                 let identifier = Ident::from_pest(&mut pairs)?;
 
-                let arguments = if rule == Rule::function_call {
-                    Vec::<FunctionCallArg>::from_pest(&mut pairs)?
-                } else {
-                    let arguments = Vec::<TrailingClosureCallArg>::from_pest(&mut pairs)?;
-                    let mut arguments = arguments
-                        .into_iter()
-                        .map(|arg| FunctionCallArg {
-                            modifier: arg.modifier,
-                            expression: arg.expression,
-                        })
-                        .collect::<Vec<_>>();
+                let mut arguments = Vec::<FunctionCallArg>::from_pest(&mut pairs)?;
+                if rule == Rule::trailing_closure_call {
                     if let Ok(closure) = Closure::from_pest(&mut pairs) {
                         arguments.push(FunctionCallArg {
+                            label: None,
                             modifier: None,
                             expression: closure.into(),
                         });
                     }
-                    arguments
-                };
+                }
 
                 Ok(Self {
                     identifier,
@@ -57,18 +58,47 @@ impl FromPest<'_> for FunctionCall {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, FromPest)]
-#[pest_ast(rule(Rule::function_call_arg))]
+#[derive(Debug, PartialEq, Eq)]
 pub struct FunctionCallArg {
+    /// Label of the argument e.g.: `to` in `move(to: origin)`
+    pub label: Option<Ident>,
     pub modifier: Option<DeclModifier>,
     pub expression: Expression,
 }
 
-#[derive(Debug, PartialEq, Eq, FromPest)]
-#[pest_ast(rule(Rule::trailing_closure_call_arg))]
-struct TrailingClosureCallArg {
-    modifier: Option<DeclModifier>,
-    expression: Expression,
+impl FromPest<'_> for FunctionCallArg {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if !matches!(
+            pair.as_rule(),
+            Rule::function_call_arg | Rule::trailing_closure_call_arg
+        ) {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        let label = match pairs.peek() {
+            Some(pair) if pair.as_rule() == Rule::argument_label => {
+                pairs.next();
+                Some(Ident::from_pest(&mut pair.into_inner())?)
+            }
+            _ => None,
+        };
+        let modifier = Option::<DeclModifier>::from_pest(&mut pairs)?;
+        let expression = Expression::from_pest(&mut pairs)?;
+
+        Ok(Self {
+            label,
+            modifier,
+            expression,
+        })
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, FromPest)]
@@ -84,3 +114,19 @@ pub struct ConstructorCallArg {
     pub ident: Ident,
     pub expression: Expression,
 }
+
+#[derive(Debug, PartialEq, Eq, FromPest)]
+#[pest_ast(rule(Rule::enum_constructor))]
+/// Creates an enum value e.g.: `Theme::Dark(name: "Night", background: black, foreground: white)`
+pub struct EnumConstructor {
+    pub enum_ident: TypeIdent,
+    pub variant: TypeIdent,
+    pub arguments: Vec<EnumConstructorArg>,
+}
+
+#[derive(Debug, PartialEq, Eq, FromPest)]
+#[pest_ast(rule(Rule::enum_constructor_arg))]
+pub struct EnumConstructorArg {
+    pub ident: Option<Ident>,
+    pub expression: Expression,
+}
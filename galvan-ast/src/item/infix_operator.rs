@@ -20,10 +20,11 @@ pub struct OperatorChain {
 #[pest_ast(rule(Rule::infix_operator))]
 pub enum InfixOperator {
     Arithmetic(ArithmeticOperator),
-    // Bitwise(BitwiseOperator),
+    Bitwise(BitwiseOperator),
     Collection(CollectionOperator),
     Comparison(ComparisonOperator),
     Logical(LogicalOperator),
+    NullCoalescing(NullCoalescingOperator),
     CustomInfix(CustomInfixOperator),
 }
 
@@ -31,9 +32,11 @@ impl BindingPower for InfixOperator {
     fn binding_power(&self) -> u8 {
         match self {
             InfixOperator::Arithmetic(op) => op.binding_power(),
+            InfixOperator::Bitwise(op) => op.binding_power(),
             InfixOperator::Collection(op) => op.binding_power(),
             InfixOperator::Comparison(op) => op.binding_power(),
             InfixOperator::Logical(op) => op.binding_power(),
+            InfixOperator::NullCoalescing(op) => op.binding_power(),
             InfixOperator::CustomInfix(op) => op.binding_power(),
         }
     }
@@ -74,6 +77,38 @@ impl FromPest<'_> for ArithmeticOperator {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitwiseOperator {
+    Or,
+    And,
+    Xor,
+    LeftShift,
+    RightShift,
+}
+
+impl FromPest<'_> for BitwiseOperator {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.next().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::bitwise_infix_operator {
+            return Err(NoMatch);
+        }
+        let pair = pair.into_inner().next().ok_or(NoMatch)?;
+        match pair.as_rule() {
+            Rule::bitwise_or => Ok(BitwiseOperator::Or),
+            Rule::bitwise_and => Ok(BitwiseOperator::And),
+            Rule::bitwise_xor => Ok(BitwiseOperator::Xor),
+            Rule::left_shift => Ok(BitwiseOperator::LeftShift),
+            Rule::right_shift => Ok(BitwiseOperator::RightShift),
+            _ => unreachable!(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CollectionOperator {
     Concat,
@@ -168,9 +203,25 @@ impl FromPest<'_> for LogicalOperator {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, FromPest)]
-#[pest_ast(rule(Rule::custom_infix_operator))]
-pub struct CustomInfixOperator(#[pest_ast(outer(with(string)))] String);
+/// The `??` operator that falls back to the right operand if the left operand has no value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPest)]
+#[pest_ast(rule(Rule::null_coalescing_operator))]
+pub struct NullCoalescingOperator;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomInfixOperator(String);
+
+from_text!(
+    CustomInfixOperator,
+    Rule::custom_infix_operator,
+    CustomInfixOperator
+);
+
+impl CustomInfixOperator {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct OperatorTree {
@@ -217,6 +268,12 @@ impl BindingPower for LogicalOperator {
     }
 }
 
+impl BindingPower for NullCoalescingOperator {
+    fn binding_power(&self) -> u8 {
+        18
+    }
+}
+
 impl BindingPower for ComparisonOperator {
     fn binding_power(&self) -> u8 {
         match self {
@@ -240,6 +297,18 @@ impl BindingPower for CollectionOperator {
     }
 }
 
+// Same relative order as in Rust, all bitwise operators bind stronger than comparisons
+impl BindingPower for BitwiseOperator {
+    fn binding_power(&self) -> u8 {
+        match self {
+            BitwiseOperator::Or => 26,
+            BitwiseOperator::Xor => 27,
+            BitwiseOperator::And => 28,
+            BitwiseOperator::LeftShift | BitwiseOperator::RightShift => 30,
+        }
+    }
+}
+
 impl BindingPower for ArithmeticOperator {
     fn binding_power(&self) -> u8 {
         match self {
@@ -252,6 +321,7 @@ impl BindingPower for ArithmeticOperator {
     }
 }
 
+// Custom operators bind stronger than all built-in operators, see the README
 impl BindingPower for CustomInfixOperator {
     fn binding_power(&self) -> u8 {
         50
@@ -3,22 +3,57 @@ use from_pest::pest::iterators::Pairs;
 use from_pest::ConversionError::NoMatch;
 use from_pest::{ConversionError, FromPest, Void};
 use galvan_pest::Rule;
+use std::borrow::Cow;
 
-use super::string;
+use super::Expression;
 
-#[derive(Debug, PartialEq, Eq, From, FromPest)]
-#[pest_ast(rule(Rule::string_literal))]
-pub struct StringLiteral(#[pest_ast(outer(with(string)))] String);
+#[derive(Debug, PartialEq, Eq)]
+pub struct StringLiteral {
+    literal: String,
+    pub segments: Vec<StringSegment>,
+}
 
 impl StringLiteral {
+    pub fn new(literal: impl Into<String>, segments: Vec<StringSegment>) -> Self {
+        Self {
+            literal: literal.into(),
+            segments,
+        }
+    }
+
     pub fn as_str(&self) -> &str {
-        &self.0
+        &self.literal
+    }
+
+    /// Content of the string without the surrounding quotes
+    pub fn value(&self) -> &str {
+        self.literal.trim_matches('\"')
+    }
+
+    pub fn interpolations(&self) -> impl Iterator<Item = &Interpolation> {
+        self.segments.iter().filter_map(|segment| match segment {
+            StringSegment::Text(_) => None,
+            StringSegment::Interpolation(interpolation) => Some(interpolation),
+        })
+    }
+}
+
+/// Creates a string literal without interpolations from its source e.g.: `"\"Hello\""`
+impl From<String> for StringLiteral {
+    fn from(literal: String) -> Self {
+        let text = literal.trim_matches('\"');
+        let segments = if text.is_empty() {
+            vec![]
+        } else {
+            vec![StringSegment::Text(text.to_owned())]
+        };
+        Self { literal, segments }
     }
 }
 
 impl From<StringLiteral> for String {
     fn from(string: StringLiteral) -> Self {
-        string.0
+        string.literal
     }
 }
 
@@ -28,10 +63,73 @@ impl AsRef<str> for StringLiteral {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, From, FromPest)]
-#[pest_ast(rule(Rule::number_literal))]
+impl FromPest<'_> for StringLiteral {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::string_literal {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let literal = pair.as_str().to_owned();
+        let mut segments = Vec::new();
+        for pair in pair.into_inner() {
+            let text: Cow<str> = match pair.as_rule() {
+                Rule::string_text => pair.as_str().into(),
+                Rule::escaped_brace => pair.as_str()[..1].into(),
+                // Raw strings are not interpolated, their content is enclosed by `#"` and escaped like other text
+                Rule::raw_string_literal => {
+                    let raw = &pair.as_str()[2..pair.as_str().len() - 2];
+                    Cow::Owned(raw.replace('\\', "\\\\").replace('"', "\\\""))
+                }
+                Rule::interpolation => {
+                    let mut pairs = pair.into_inner();
+                    let expression = Expression::from_pest(&mut pairs)?;
+                    let format_spec = pairs.next().map(|spec| spec.as_str()[1..].to_owned());
+                    segments.push(StringSegment::Interpolation(Interpolation {
+                        expression,
+                        format_spec,
+                    }));
+                    continue;
+                }
+                _ => return Err(NoMatch),
+            };
+
+            match segments.last_mut() {
+                Some(StringSegment::Text(previous)) => previous.push_str(&text),
+                _ => segments.push(StringSegment::Text(text.into_owned())),
+            }
+        }
+
+        Ok(Self { literal, segments })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, From)]
+pub enum StringSegment {
+    /// Text of the string, escape sequences like `\n` are kept as written and escaped braces are unescaped
+    Text(String),
+    Interpolation(Interpolation),
+}
+
+/// Value that is formatted into a string e.g.: `{self.name}` or `{price:.2}`
+#[derive(Debug, PartialEq, Eq)]
+pub struct Interpolation {
+    pub expression: Expression,
+    /// Format specification after the colon, as in Rust's `format!` e.g.: `.2` or `?`
+    pub format_spec: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, From)]
 // TODO: Parse number literal and validate type
-pub struct NumberLiteral(#[pest_ast(outer(with(string)))] String);
+pub struct NumberLiteral(String);
+
+from_text!(NumberLiteral, Rule::number_literal, NumberLiteral);
 
 impl NumberLiteral {
     pub fn new(value: &str) -> Self {
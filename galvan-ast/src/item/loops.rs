@@ -0,0 +1,106 @@
+use from_pest::pest::iterators::Pairs;
+use from_pest::ConversionError::NoMatch;
+use from_pest::{ConversionError, FromPest, Void};
+use galvan_pest::Rule;
+
+use super::*;
+
+#[derive(Debug, PartialEq, Eq)]
+/// Iterates over a range or collection e.g.: `for 0..<n { print(it) }` or `for items |item| { print(item) }`
+pub struct ForLoop {
+    pub label: Option<Label>,
+    pub iterable: Expression,
+    /// Named loop variables, the loop variable is called `it` if this is empty
+    pub arguments: Vec<ClosureArgument>,
+    pub body: Block,
+}
+
+impl FromPest<'_> for ForLoop {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::for_loop {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        let label = Option::<Label>::from_pest(&mut pairs)?;
+        let iterable = Expression::from_pest(&mut pairs)?;
+        let arguments = Vec::<ClosureArgument>::from_pest(&mut pairs)?;
+        let body = Block::from_pest(&mut pairs)?;
+
+        Ok(Self {
+            label,
+            iterable,
+            arguments,
+            body,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, FromPest)]
+#[pest_ast(rule(Rule::while_loop))]
+/// Runs the body as long as the condition is true e.g.: `while i < 10 { i += 1 }`
+pub struct WhileLoop {
+    pub label: Option<Label>,
+    pub condition: Expression,
+    pub body: Block,
+}
+
+#[derive(Debug, PartialEq, Eq, FromPest)]
+#[pest_ast(rule(Rule::loop_expression))]
+/// Runs the body until the loop is exited with `break`, the loop has the value that is passed to `break`
+pub struct LoopExpression {
+    pub label: Option<Label>,
+    pub body: Block,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, FromPest)]
+#[pest_ast(rule(Rule::label))]
+/// Names a loop, so `break` and `continue` can refer to an outer loop e.g.: `'outer`
+pub struct Label {
+    pub ident: Ident,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+/// Exits a loop, optionally yielding a value from a `loop` e.g.: `break 'outer value`
+pub struct Break {
+    pub label: Option<Label>,
+    pub value: Option<Expression>,
+}
+
+impl FromPest<'_> for Break {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::break_statement {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        let label = Option::<Label>::from_pest(&mut pairs)?;
+        let value = match pairs.next() {
+            Some(value) => Some(exit_value(value)?),
+            None => None,
+        };
+
+        Ok(Self { label, value })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, FromPest)]
+#[pest_ast(rule(Rule::continue_statement))]
+/// Skips to the next iteration of a loop e.g.: `continue 'outer`
+pub struct Continue {
+    pub label: Option<Label>,
+}
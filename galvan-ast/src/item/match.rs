@@ -0,0 +1,178 @@
+use from_pest::pest::iterators::Pairs;
+use from_pest::ConversionError::NoMatch;
+use from_pest::{ConversionError, FromPest, Void};
+use galvan_pest::Rule;
+
+use super::*;
+
+#[derive(Debug, PartialEq, Eq)]
+/// A match expression e.g.: `match theme { Theme::Dark(background, foreground) => background, _ => white }`
+pub struct MatchExpression {
+    pub subject: Box<Expression>,
+    pub arms: Vec<MatchArm>,
+}
+
+impl FromPest<'_> for MatchExpression {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::match_expression {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        let subject = Box::new(Expression::from_pest(&mut pairs)?);
+        let arms = Vec::<MatchArm>::from_pest(&mut pairs)?;
+
+        Ok(Self { subject, arms })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expression>,
+    pub body: Block,
+}
+
+impl MatchArm {
+    /// Returns true if this arm matches every value that reaches it
+    pub fn is_catch_all(&self) -> bool {
+        self.guard.is_none() && self.pattern.is_irrefutable()
+    }
+}
+
+impl FromPest<'_> for MatchArm {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::match_arm {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        let pattern = Pattern::from_pest(&mut pairs)?;
+        let guard = match pairs.peek() {
+            Some(guard) if guard.as_rule() == Rule::match_guard => {
+                pairs.next();
+                Some(Expression::from_pest(&mut guard.into_inner())?)
+            }
+            _ => None,
+        };
+        let body = match pairs.peek().ok_or(NoMatch)?.as_rule() {
+            Rule::block => Block::from_pest(&mut pairs)?,
+            _ => Block {
+                body: Body {
+                    statements: vec![TopExpression::from_pest(&mut pairs)?.into()],
+                },
+            },
+        };
+
+        Ok(Self {
+            pattern,
+            guard,
+            body,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Pattern {
+    /// `_` matches anything without binding it
+    Wildcard,
+    /// Binds the matched value to a new variable
+    Binding(Ident),
+    Literal(Literal),
+    Tuple(Vec<Pattern>),
+    Enum(EnumPattern),
+    /// `some(pattern)` matches an optional that contains a value
+    Some(Box<Pattern>),
+    /// `none` matches an empty optional
+    None,
+}
+
+impl Pattern {
+    /// Returns true if this pattern matches every value of its type
+    pub fn is_irrefutable(&self) -> bool {
+        match self {
+            Pattern::Wildcard | Pattern::Binding(_) => true,
+            Pattern::Tuple(elements) => elements.iter().all(Pattern::is_irrefutable),
+            Pattern::Literal(_) | Pattern::Enum(_) | Pattern::Some(_) | Pattern::None => false,
+        }
+    }
+
+    /// All variables that are bound by this pattern
+    pub fn bindings(&self) -> Vec<&Ident> {
+        match self {
+            Pattern::Binding(ident) => vec![ident],
+            Pattern::Tuple(elements) => elements.iter().flat_map(Pattern::bindings).collect(),
+            Pattern::Enum(pattern) => pattern
+                .fields
+                .iter()
+                .flat_map(|field| field.pattern.bindings())
+                .collect(),
+            Pattern::Some(inner) => inner.bindings(),
+            Pattern::Wildcard | Pattern::Literal(_) | Pattern::None => vec![],
+        }
+    }
+}
+
+impl FromPest<'_> for Pattern {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::pattern {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        let inner = pairs.peek().ok_or(NoMatch)?;
+        let pattern = match inner.as_rule() {
+            Rule::wildcard_pattern => Pattern::Wildcard,
+            Rule::none_pattern => Pattern::None,
+            Rule::ident => Pattern::Binding(Ident::from_pest(&mut pairs)?),
+            Rule::literal => Pattern::Literal(Literal::from_pest(&mut pairs)?),
+            Rule::tuple_pattern => {
+                Pattern::Tuple(Vec::<Pattern>::from_pest(&mut inner.into_inner())?)
+            }
+            Rule::enum_pattern => Pattern::Enum(EnumPattern::from_pest(&mut pairs)?),
+            Rule::some_pattern => {
+                Pattern::Some(Box::new(Pattern::from_pest(&mut inner.into_inner())?))
+            }
+            _ => unreachable!("Unexpected rule in pattern: {:?}", inner.as_rule()),
+        };
+
+        Ok(pattern)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, FromPest)]
+#[pest_ast(rule(Rule::enum_pattern))]
+/// Matches an enum variant e.g.: `Theme::Dark(background, foreground: _)`
+pub struct EnumPattern {
+    pub enum_ident: TypeIdent,
+    pub variant: TypeIdent,
+    pub fields: Vec<EnumPatternField>,
+}
+
+#[derive(Debug, PartialEq, Eq, FromPest)]
+#[pest_ast(rule(Rule::enum_pattern_field))]
+pub struct EnumPatternField {
+    pub ident: Option<Ident>,
+    pub pattern: Pattern,
+}
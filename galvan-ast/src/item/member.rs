@@ -45,7 +45,7 @@ impl FromPest<'_> for MemberChain {
         // println!("Member chain base: {:#?}", pair);
         let mut pairs = pair.into_inner();
         let mut elements = Vec::new();
-        while let Some(_) = pairs.peek() {
+        while pairs.peek().is_some() {
             // println!("Member chain element: {:#?}", pair);
             // println!("Member chain: {:#?}", elements);
             elements.push(SingleExpression::from_pest(&mut pairs)?);
@@ -1,14 +1,44 @@
+/// Implements `FromPest` for items that consist of the text matched by a rule
+///
+/// Deriving these with `#[pest_ast(outer(with(...)))]` clones the span of the pair, which is `Copy`
+macro_rules! from_text {
+    ($ty:ty, $rule:path, $convert:expr) => {
+        impl ::from_pest::FromPest<'_> for $ty {
+            type Rule = ::galvan_pest::Rule;
+            type FatalError = ::from_pest::Void;
+
+            fn from_pest(
+                pairs: &mut ::from_pest::pest::iterators::Pairs<'_, Self::Rule>,
+            ) -> ::std::result::Result<Self, ::from_pest::ConversionError<Self::FatalError>> {
+                match pairs.peek() {
+                    Some(pair) if pair.as_rule() == $rule => {
+                        pairs.next();
+                        Ok($convert(pair.as_str().to_owned()))
+                    }
+                    _ => Err(::from_pest::ConversionError::NoMatch),
+                }
+            }
+        }
+    };
+}
+
 mod assignment;
 mod closure;
 mod collection;
+mod r#const;
+mod exit;
 mod r#fn;
 mod function_call;
 mod ident;
+mod infix_operator;
 mod literal;
+mod loops;
+mod r#match;
 mod member;
 mod modifier;
-mod infix_operator;
 mod postfix;
+mod prefix;
+mod range;
 mod statement;
 mod tasks;
 mod toplevel;
@@ -18,20 +48,22 @@ mod type_item;
 pub use assignment::*;
 pub use closure::*;
 pub use collection::*;
+pub use exit::*;
 pub use function_call::*;
 pub use ident::*;
+pub use infix_operator::*;
 pub use literal::*;
+pub use loops::*;
 pub use member::*;
 pub use modifier::*;
-pub use infix_operator::*;
 pub use postfix::*;
+pub use prefix::*;
+pub use r#const::*;
 pub use r#fn::*;
+pub use r#match::*;
 pub use r#type::*;
+pub use range::*;
 pub use statement::*;
 pub use tasks::*;
 pub use toplevel::*;
 pub use type_item::*;
-
-fn string(span: galvan_pest::BorrowedSpan<'_>) -> String {
-    span.as_str().to_owned()
-}
@@ -1,3 +1,6 @@
+use from_pest::pest::iterators::Pairs;
+use from_pest::ConversionError::NoMatch;
+use from_pest::{ConversionError, FromPest, Void};
 use galvan_pest::Rule;
 
 #[derive(Clone, Default, Debug)]
@@ -68,6 +71,32 @@ pub enum Async {
     Generic,
 }
 
+impl Async {
+    pub fn is_async(&self) -> bool {
+        matches!(self, Async::Async)
+    }
+}
+
+impl FromPest<'_> for Async {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::asyncness {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        match pair.into_inner().next() {
+            Some(_) => Ok(Async::Async),
+            None => Ok(Async::Inherited),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
 pub enum Const {
     Const,
@@ -77,3 +106,29 @@ pub enum Const {
     // This is not implemented but will be supported in future versions
     Generic,
 }
+
+impl Const {
+    pub fn is_const(&self) -> bool {
+        matches!(self, Const::Const)
+    }
+}
+
+impl FromPest<'_> for Const {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::constness {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        match pair.into_inner().next() {
+            Some(_) => Ok(Const::Const),
+            None => Ok(Const::Inherited),
+        }
+    }
+}
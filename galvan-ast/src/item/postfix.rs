@@ -1,13 +1,14 @@
 use crate::{Expression, SingleExpression};
 use derive_more::From;
 use from_pest::pest::iterators::Pairs;
-use from_pest::{ConversionError, Void};
+use from_pest::{ConversionError, FromPest, Void};
 use galvan_pest::Rule;
 use typeunion::type_union;
 
 #[type_union]
 #[derive(Debug, PartialEq, Eq)]
-pub type PostfixExpression = YeetExpression + AccessExpression;
+pub type PostfixExpression =
+    YeetExpression + SafeCallExpression + AccessExpression + SliceExpression;
 
 pub(crate) fn handle_postfixes(
     base: SingleExpression,
@@ -25,8 +26,28 @@ pub(crate) fn handle_postfixes(
             Rule::yeet_operator => {
                 SingleExpression::Postfix(PostfixExpression::YeetExpression(exp.into()).into())
             }
+            Rule::safe_call_operator => {
+                SingleExpression::Postfix(PostfixExpression::SafeCallExpression(exp.into()).into())
+            }
             Rule::access_operator => {
-                todo!("Implement access operator AST conversion!");
+                let index = Expression::from_pest(&mut inner.into_inner())?;
+                SingleExpression::Postfix(
+                    PostfixExpression::AccessExpression(AccessExpression { base: exp, index })
+                        .into(),
+                )
+            }
+            Rule::slice_operator => {
+                let mut bounds = inner.into_inner();
+                let start = slice_bound(&mut bounds, Rule::slice_start)?;
+                let end = slice_bound(&mut bounds, Rule::slice_end)?;
+                SingleExpression::Postfix(
+                    PostfixExpression::SliceExpression(SliceExpression {
+                        base: exp,
+                        start,
+                        end,
+                    })
+                    .into(),
+                )
             }
             _ => unreachable!("Unexpected postfix operator rule"),
         }
@@ -35,6 +56,19 @@ pub(crate) fn handle_postfixes(
     Ok(exp)
 }
 
+fn slice_bound(
+    pairs: &mut Pairs<'_, Rule>,
+    rule: Rule,
+) -> Result<Option<Expression>, ConversionError<Void>> {
+    match pairs.peek() {
+        Some(pair) if pair.as_rule() == rule => {
+            pairs.next();
+            Ok(Some(Expression::from_pest(&mut pair.into_inner())?))
+        }
+        _ => Ok(None),
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct AccessExpression {
     pub base: SingleExpression,
@@ -43,3 +77,15 @@ pub struct AccessExpression {
 
 #[derive(Debug, From, PartialEq, Eq)]
 pub struct YeetExpression(pub SingleExpression);
+
+/// Slices an array or string e.g.: `text[1:3]`, both bounds are optional
+#[derive(Debug, PartialEq, Eq)]
+pub struct SliceExpression {
+    pub base: SingleExpression,
+    pub start: Option<Expression>,
+    pub end: Option<Expression>,
+}
+
+/// Marks the receiver of a safe call e.g.: `receiver?.member`
+#[derive(Debug, From, PartialEq, Eq)]
+pub struct SafeCallExpression(pub SingleExpression);
@@ -0,0 +1,86 @@
+use super::*;
+use from_pest::pest::iterators::Pairs;
+use from_pest::ConversionError::NoMatch;
+use from_pest::{ConversionError, FromPest, Void};
+use galvan_pest::Rule;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PrefixExpression {
+    pub operator: PrefixOperator,
+    pub operand: Box<SimpleExpression>,
+}
+
+impl FromPest<'_> for PrefixExpression {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::prefix_expression {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        let operator = PrefixOperator::from_pest(&mut pairs)?;
+        let operand: SimpleExpression = match pairs.peek().ok_or(NoMatch)?.as_rule() {
+            Rule::prefix_expression => PrefixExpression::from_pest(&mut pairs)?.into(),
+            Rule::member_chain => MemberChain::from_pest(&mut pairs)?.into(),
+            _ => SingleExpression::from_pest(&mut pairs)?.into(),
+        };
+
+        Ok(PrefixExpression {
+            operator,
+            operand: Box::new(operand),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrefixOperator {
+    Not,
+    Negate,
+    BitwiseNot,
+    CustomPrefix(CustomPrefixOperator),
+}
+
+impl FromPest<'_> for PrefixOperator {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.next().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::prefix_operator {
+            return Err(NoMatch);
+        }
+        let pair = pair.into_inner().next().ok_or(NoMatch)?;
+        match pair.as_rule() {
+            Rule::not_operator => Ok(PrefixOperator::Not),
+            Rule::negate_operator => Ok(PrefixOperator::Negate),
+            Rule::bitwise_not => Ok(PrefixOperator::BitwiseNot),
+            Rule::custom_prefix_operator => Ok(PrefixOperator::CustomPrefix(
+                CustomPrefixOperator::from_pest(&mut Pairs::single(pair))?,
+            )),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomPrefixOperator(String);
+
+from_text!(
+    CustomPrefixOperator,
+    Rule::custom_prefix_operator,
+    CustomPrefixOperator
+);
+
+impl CustomPrefixOperator {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
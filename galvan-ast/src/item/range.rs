@@ -0,0 +1,77 @@
+use from_pest::pest::iterators::Pairs;
+use from_pest::ConversionError::NoMatch;
+use from_pest::{ConversionError, FromPest, Void};
+use galvan_pest::Rule;
+
+use super::*;
+
+#[derive(Debug, PartialEq, Eq)]
+/// A range of values e.g.: `0..<n` or `1..=10`
+pub struct RangeExpression {
+    pub start: Box<Expression>,
+    pub kind: RangeKind,
+    pub end: Box<Expression>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeKind {
+    /// `..<` excludes the upper bound
+    Exclusive,
+    /// `..=` includes the upper bound
+    Inclusive,
+}
+
+impl FromPest<'_> for RangeExpression {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::range {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        let start = Box::new(range_bound(&mut pairs)?);
+        let kind = RangeKind::from_pest(&mut pairs)?;
+        let end = Box::new(range_bound(&mut pairs)?);
+
+        Ok(Self { start, kind, end })
+    }
+}
+
+impl FromPest<'_> for RangeKind {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.next().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::range_operator {
+            return Err(NoMatch);
+        }
+        let pair = pair.into_inner().next().ok_or(NoMatch)?;
+        match pair.as_rule() {
+            Rule::exclusive_range => Ok(RangeKind::Exclusive),
+            Rule::inclusive_range => Ok(RangeKind::Inclusive),
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn range_bound(pairs: &mut Pairs<'_, Rule>) -> Result<Expression, ConversionError<Void>> {
+    let pair = pairs.next().ok_or(NoMatch)?;
+    if pair.as_rule() != Rule::range_bound {
+        return Err(NoMatch);
+    }
+
+    let mut pairs = pair.into_inner();
+    match pairs.peek().ok_or(NoMatch)?.as_rule() {
+        Rule::operator_chain => Ok(OperatorTree::from_pest(&mut pairs)?.into()),
+        _ => Ok(SimpleExpression::from_pest(&mut pairs)?.into()),
+    }
+}
@@ -21,7 +21,8 @@ pub struct Block {
 #[type_union]
 #[derive(Debug, PartialEq, Eq, FromPest)]
 #[pest_ast(rule(Rule::statement))]
-pub type Statement = Assignment + Declaration + TopExpression + Block;
+pub type Statement =
+    Assignment + Declaration + ForLoop + WhileLoop + Break + Continue + TopExpression + Block;
 
 #[derive(Debug, PartialEq, Eq, FromPest)]
 #[pest_ast(rule(Rule::declaration))]
@@ -40,19 +41,39 @@ pub type Literal = BooleanLiteral + StringLiteral + NumberLiteral;
 #[type_union]
 #[derive(Debug, PartialEq, Eq, FromPest)]
 #[pest_ast(rule(Rule::expression))]
-pub type Expression =
-    OperatorTree + MemberChain + SingleExpression + Closure;
+pub type Expression = MatchExpression
+    + LoopExpression
+    + Return
+    + Throw
+    + RangeExpression
+    + OperatorTree
+    + PrefixExpression
+    + MemberChain
+    + SingleExpression
+    + Closure;
 
 #[type_union(super = Expression)]
 #[derive(Debug, PartialEq, Eq, FromPest)]
 #[pest_ast(rule(Rule::simple_expression))]
-pub type SimpleExpression = MemberChain + SingleExpression;
+pub type SimpleExpression = PrefixExpression + MemberChain + SingleExpression;
 
 pub type Postfix = Box<PostfixExpression>;
 #[type_union]
 #[derive(Debug, PartialEq, Eq)]
-pub type SingleExpression =
-    Postfix + CollectionLiteral + FunctionCall + ConstructorCall + Literal + Ident;
+pub type SingleExpression = Postfix
+    + CollectionLiteral
+    + FunctionCall
+    + ConstructorCall
+    + EnumConstructor
+    + Literal
+    + Group
+    + Block
+    + Ident;
+
+/// Parenthesized expression e.g.: `(a + b) * c`
+#[derive(Debug, PartialEq, Eq, FromPest)]
+#[pest_ast(rule(Rule::group))]
+pub struct Group(pub Box<TopExpression>);
 
 impl FromPest<'_> for SingleExpression {
     type Rule = Rule;
@@ -86,10 +107,26 @@ impl FromPest<'_> for SingleExpression {
                         let constructor_call = ConstructorCall::from_pest(&mut pairs)?;
                         Ok(constructor_call.into())
                     }
+                    Rule::enum_constructor => {
+                        let enum_constructor = EnumConstructor::from_pest(&mut pairs)?;
+                        Ok(enum_constructor.into())
+                    }
                     Rule::literal => {
                         let literal = Literal::from_pest(&mut pairs)?;
                         Ok(literal.into())
                     }
+                    Rule::group => {
+                        let group = Group::from_pest(&mut pairs)?;
+                        Ok(group.into())
+                    }
+                    Rule::block => {
+                        let block = Block::from_pest(&mut pairs)?;
+                        Ok(block.into())
+                    }
+                    Rule::numbered_parameter => {
+                        let pair = pairs.next().ok_or(NoMatch)?;
+                        Ok(Ident::new(pair.as_str()).into())
+                    }
                     Rule::ident => {
                         let ident = Ident::from_pest(&mut pairs)?;
                         Ok(ident.into())
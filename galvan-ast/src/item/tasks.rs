@@ -1,16 +1,18 @@
 use galvan_pest::Rule;
 
-use super::{Body, Ident, StringLiteral};
+use super::{Async, Body, Ident, StringLiteral};
 
 #[derive(Debug, PartialEq, Eq, FromPest)]
 #[pest_ast(rule(Rule::main))]
 pub struct MainDecl {
+    pub asyncness: Async,
     pub body: Body,
 }
 
 #[derive(Debug, PartialEq, Eq, FromPest)]
 #[pest_ast(rule(Rule::test))]
 pub struct TestDecl {
+    pub asyncness: Async,
     pub name: Option<StringLiteral>,
     pub body: Body,
 }
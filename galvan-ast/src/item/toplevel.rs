@@ -1,13 +1,14 @@
 use derive_more::From;
 use galvan_pest::Rule;
 
-use super::{FnDecl, MainDecl, TestDecl, TypeDecl};
+use super::{ConstDecl, FnDecl, MainDecl, TestDecl, TypeDecl};
 
 #[derive(Debug, PartialEq, Eq, From, FromPest)]
 #[pest_ast(rule(Rule::toplevel))]
 pub enum RootItem {
     Fn(FnDecl),
     Type(TypeDecl),
+    Const(ConstDecl),
     Main(MainDecl),
     Test(TestDecl),
     // CustomTask(TaskDecl),
@@ -27,6 +28,10 @@ impl private::Seal for TypeDecl {}
 
 impl RootItemMarker for TypeDecl {}
 
+impl private::Seal for ConstDecl {}
+
+impl RootItemMarker for ConstDecl {}
+
 impl private::Seal for MainDecl {}
 
 impl RootItemMarker for MainDecl {}
@@ -1,11 +1,15 @@
 use derive_more::From;
+use from_pest::pest::iterators::Pairs;
+use from_pest::ConversionError::NoMatch;
+use from_pest::{ConversionError, FromPest, Void};
 use galvan_pest::Rule;
 
-use super::{DeclModifier, Ident, TypeElement, TypeIdent, Visibility};
+use super::{DeclModifier, Ident, TypeElement, TypeIdent, Visibility, WhereClause};
 
 #[derive(Debug, PartialEq, Eq, From, FromPest)]
 #[pest_ast(rule(Rule::type_decl))]
 pub enum TypeDecl {
+    Enum(EnumTypeDecl),
     Tuple(TupleTypeDecl),
     Struct(StructTypeDecl),
     Alias(AliasTypeDecl),
@@ -15,12 +19,132 @@ pub enum TypeDecl {
 impl TypeDecl {
     pub fn ident(&self) -> &TypeIdent {
         match self {
+            TypeDecl::Enum(e) => &e.ident,
             TypeDecl::Tuple(t) => &t.ident,
             TypeDecl::Struct(s) => &s.ident,
             TypeDecl::Alias(a) => &a.ident,
             TypeDecl::Empty(e) => &e.ident,
         }
     }
+
+    pub fn where_clause(&self) -> Option<&WhereClause> {
+        match self {
+            TypeDecl::Enum(e) => e.where_clause.as_ref(),
+            TypeDecl::Tuple(t) => t.where_clause.as_ref(),
+            TypeDecl::Struct(s) => s.where_clause.as_ref(),
+            TypeDecl::Alias(a) => a.where_clause.as_ref(),
+            TypeDecl::Empty(_) => None,
+        }
+    }
+
+    /// Type parameters of this type in order of their first appearance
+    pub fn generics(&self) -> Vec<&TypeIdent> {
+        match self {
+            TypeDecl::Enum(e) => e.generics(),
+            TypeDecl::Tuple(t) => t.generics(),
+            TypeDecl::Struct(s) => s.generics(),
+            TypeDecl::Alias(a) => a.generics(),
+            TypeDecl::Empty(_) => vec![],
+        }
+    }
+}
+
+fn collect_member_generics<'a>(members: &'a [StructTypeMember], generics: &mut Vec<&'a TypeIdent>) {
+    members
+        .iter()
+        .for_each(|member| member.r#type.collect_generics(generics));
+}
+
+#[derive(Debug, PartialEq, Eq, FromPest)]
+#[pest_ast(rule(Rule::enum_type_decl))]
+/// An enum type e.g.: `type Theme(name: String) { Plain, Dark(background: Color) }`
+pub struct EnumTypeDecl {
+    pub visibility: Visibility,
+    pub ident: TypeIdent,
+    /// Fields that are shared by all variants
+    pub fields: Vec<StructTypeMember>,
+    pub where_clause: Option<WhereClause>,
+    pub variants: Vec<EnumTypeVariant>,
+}
+
+impl EnumTypeDecl {
+    pub fn variant(&self, ident: &TypeIdent) -> Option<&EnumTypeVariant> {
+        self.variants.iter().find(|variant| variant.ident == *ident)
+    }
+
+    pub fn has_shared_fields(&self) -> bool {
+        !self.fields.is_empty()
+    }
+
+    /// Type parameters of the shared fields and all variants in order of their first appearance
+    pub fn generics(&self) -> Vec<&TypeIdent> {
+        let mut generics = vec![];
+        collect_member_generics(&self.fields, &mut generics);
+        self.collect_variant_generics(&mut generics);
+        generics
+    }
+
+    /// Type parameters that are used by the variants, excluding the shared fields
+    pub fn variant_generics(&self) -> Vec<&TypeIdent> {
+        let mut generics = vec![];
+        self.collect_variant_generics(&mut generics);
+        generics
+    }
+
+    fn collect_variant_generics<'a>(&'a self, generics: &mut Vec<&'a TypeIdent>) {
+        for variant in &self.variants {
+            match &variant.fields {
+                EnumVariantFields::Unit => {}
+                EnumVariantFields::Tuple(members) => members
+                    .iter()
+                    .for_each(|member| member.r#type.collect_generics(generics)),
+                EnumVariantFields::Struct(members) => collect_member_generics(members, generics),
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct EnumTypeVariant {
+    pub ident: TypeIdent,
+    pub fields: EnumVariantFields,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EnumVariantFields {
+    Unit,
+    Tuple(Vec<TupleTypeMember>),
+    Struct(Vec<StructTypeMember>),
+}
+
+impl FromPest<'_> for EnumTypeVariant {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(
+        pairs: &mut Pairs<'_, Self::Rule>,
+    ) -> Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(NoMatch)?;
+        if pair.as_rule() != Rule::enum_variant {
+            return Err(NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        let ident = TypeIdent::from_pest(&mut pairs)?;
+        let fields = match pairs.next() {
+            None => EnumVariantFields::Unit,
+            Some(pair) if pair.as_rule() == Rule::enum_tuple_fields => {
+                EnumVariantFields::Tuple(Vec::<TupleTypeMember>::from_pest(&mut pair.into_inner())?)
+            }
+            Some(pair) if pair.as_rule() == Rule::enum_struct_fields => EnumVariantFields::Struct(
+                Vec::<StructTypeMember>::from_pest(&mut pair.into_inner())?,
+            ),
+            Some(_) => unreachable!("Unexpected rule in enum variant"),
+        };
+
+        Ok(Self { ident, fields })
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, FromPest)]
@@ -29,6 +153,18 @@ pub struct TupleTypeDecl {
     pub visibility: Visibility,
     pub ident: TypeIdent,
     pub members: Vec<TupleTypeMember>,
+    pub where_clause: Option<WhereClause>,
+}
+
+impl TupleTypeDecl {
+    /// Type parameters of the members in order of their first appearance
+    pub fn generics(&self) -> Vec<&TypeIdent> {
+        let mut generics = vec![];
+        self.members
+            .iter()
+            .for_each(|member| member.r#type.collect_generics(&mut generics));
+        generics
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, FromPest)]
@@ -43,9 +179,19 @@ pub struct TupleTypeMember {
 pub struct StructTypeDecl {
     pub visibility: Visibility,
     pub ident: TypeIdent,
+    pub where_clause: Option<WhereClause>,
     pub members: Vec<StructTypeMember>,
 }
 
+impl StructTypeDecl {
+    /// Type parameters of the members in order of their first appearance
+    pub fn generics(&self) -> Vec<&TypeIdent> {
+        let mut generics = vec![];
+        collect_member_generics(&self.members, &mut generics);
+        generics
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, FromPest)]
 #[pest_ast(rule(Rule::struct_field))]
 pub struct StructTypeMember {
@@ -61,6 +207,16 @@ pub struct AliasTypeDecl {
     pub visibility: Visibility,
     pub ident: TypeIdent,
     pub r#type: TypeElement,
+    pub where_clause: Option<WhereClause>,
+}
+
+impl AliasTypeDecl {
+    /// Type parameters of the aliased type in order of their first appearance
+    pub fn generics(&self) -> Vec<&TypeIdent> {
+        let mut generics = vec![];
+        self.r#type.collect_generics(&mut generics);
+        generics
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, FromPest)]
@@ -2,7 +2,7 @@ use from_pest::{pest, ConversionError, FromPest};
 use galvan_pest::Rule;
 use typeunion::type_union;
 
-use crate::TypeIdent;
+use crate::{DeclModifier, TypeIdent};
 
 type Array = Box<ArrayTypeItem>;
 type Dictionary = Box<DictionaryTypeItem>;
@@ -12,40 +12,80 @@ type Tuple = Box<TupleTypeItem>;
 type Optional = Box<OptionalTypeItem>;
 type Result = Box<ResultTypeItem>;
 type Plain = BasicTypeItem;
+type Generic = GenericTypeItem;
+type Union = Box<UnionTypeItem>;
+type Function = Box<FunctionTypeItem>;
 
 #[type_union]
 #[derive(Clone, Debug, PartialEq, Eq, Hash, FromPest)]
 #[pest_ast(rule(Rule::type_item))]
-pub type TypeElement =
-    Array + Dictionary + OrderedDictionary + Set + Tuple + Optional + Result + Plain;
+pub type TypeElement = Array
+    + Dictionary
+    + OrderedDictionary
+    + Set
+    + Tuple
+    + Optional
+    + Result
+    + Plain
+    + Generic
+    + Union
+    + Function;
 
 #[type_union(super = TypeElement)]
 #[derive(Clone, Debug, PartialEq, Eq, FromPest)]
 #[pest_ast(rule(Rule::opt_element_type))]
 /// A subset of TypeElement that can be used as the inner type of an optional
-type OptionalElement = Array + Dictionary + OrderedDictionary + Set + Tuple + Plain;
+type OptionalElement = Array + Dictionary + OrderedDictionary + Set + Tuple + Plain + Generic;
 
 #[type_union(super = TypeElement)]
 #[derive(Clone, Debug, PartialEq, Eq, FromPest)]
 #[pest_ast(rule(Rule::success_variant))]
 /// A subset of TypeElement that can be used as the success variant of a result type
-type SuccessVariant = Array + Dictionary + OrderedDictionary + Set + Tuple + Optional + Plain;
+type SuccessVariant =
+    Array + Dictionary + OrderedDictionary + Set + Tuple + Optional + Plain + Generic;
 
 #[type_union(super = TypeElement)]
 #[derive(Clone, Debug, PartialEq, Eq, FromPest)]
 #[pest_ast(rule(Rule::error_variant))]
 /// A subset of TypeElement that can be used as the error variant of a result type
-type ErrorVariant = Array + Dictionary + OrderedDictionary + Set + Tuple + Plain;
+type ErrorVariant = Array + Dictionary + OrderedDictionary + Set + Tuple + Plain + Generic;
+
+#[type_union(super = TypeElement)]
+#[derive(Clone, Debug, PartialEq, Eq, FromPest)]
+#[pest_ast(rule(Rule::union_element))]
+/// A subset of TypeElement that can be used as a member of a union type
+type UnionElement = Array + Dictionary + OrderedDictionary + Set + Tuple + Plain + Generic;
+
+#[type_union(super = TypeElement)]
+#[derive(Clone, Debug, PartialEq, Eq, FromPest)]
+#[pest_ast(rule(Rule::closure_argument_type))]
+/// A subset of TypeElement that can be used as the type of a closure argument
+pub(crate) type ClosureArgumentType =
+    Array + Dictionary + OrderedDictionary + Set + Tuple + Optional + Result + Plain + Generic;
+
+#[type_union(super = TypeElement)]
+#[derive(Clone, Debug, PartialEq, Eq, FromPest)]
+#[pest_ast(rule(Rule::single_param_type))]
+/// A subset of TypeElement that can be used as the only parameter of a function type without parentheses
+type SingleParamType =
+    Array + Dictionary + OrderedDictionary + Set + Optional + Result + Plain + Generic;
 
 impl From<TypeIdent> for TypeElement {
     fn from(value: TypeIdent) -> Self {
-        Self::Plain(BasicTypeItem { ident: value })
+        Self::plain(value)
     }
 }
 
 impl TypeElement {
     pub fn plain(ident: TypeIdent) -> Self {
-        Self::Plain(BasicTypeItem { ident })
+        Self::Plain(BasicTypeItem {
+            ident,
+            generics: vec![],
+        })
+    }
+
+    pub fn generic(ident: TypeIdent) -> Self {
+        Self::Generic(GenericTypeItem { ident })
     }
 
     pub fn array(elements: TypeElement) -> Self {
@@ -75,6 +115,75 @@ impl TypeElement {
     pub fn result(success: TypeElement, error: Option<TypeElement>) -> Self {
         Self::Result(Box::new(ResultTypeItem { success, error }))
     }
+
+    /// Creates a union of both types, nested unions are flattened and duplicate members are removed
+    pub fn union(left: TypeElement, right: TypeElement) -> Self {
+        let mut elements: Vec<TypeElement> = vec![];
+        for ty in [left, right] {
+            let members = match ty {
+                TypeElement::Union(union) => union.elements,
+                ty => vec![ty],
+            };
+            for member in members {
+                if !elements.contains(&member) {
+                    elements.push(member);
+                }
+            }
+        }
+
+        match elements.len() {
+            1 => elements.remove(0),
+            _ => Self::Union(Box::new(UnionTypeItem { elements })),
+        }
+    }
+
+    /// All type parameters that are used in this type in order of their first appearance
+    pub fn generics(&self) -> Vec<&TypeIdent> {
+        let mut generics = vec![];
+        self.collect_generics(&mut generics);
+        generics
+    }
+
+    /// Adds all type parameters that are used in this type to `generics`, skipping duplicates
+    pub fn collect_generics<'a>(&'a self, generics: &mut Vec<&'a TypeIdent>) {
+        let mut add = |ty: &'a TypeElement| ty.collect_generics(generics);
+        match self {
+            TypeElement::Generic(generic) => {
+                if !generics.contains(&&generic.ident) {
+                    generics.push(&generic.ident);
+                }
+            }
+            TypeElement::Plain(plain) => plain.generics.iter().for_each(add),
+            TypeElement::Array(array) => add(&array.elements),
+            TypeElement::Set(set) => add(&set.elements),
+            TypeElement::Dictionary(dict) => {
+                add(&dict.key);
+                add(&dict.value);
+            }
+            TypeElement::OrderedDictionary(dict) => {
+                add(&dict.key);
+                add(&dict.value);
+            }
+            TypeElement::Tuple(tuple) => tuple.elements.iter().for_each(add),
+            TypeElement::Union(union) => union.elements.iter().for_each(add),
+            TypeElement::Optional(optional) => add(&optional.some),
+            TypeElement::Result(result) => {
+                add(&result.success);
+                if let Some(error) = &result.error {
+                    add(error);
+                }
+            }
+            TypeElement::Function(function) => {
+                function
+                    .parameters
+                    .iter()
+                    .for_each(|param| add(&param.param_type));
+                if let Some(return_type) = &function.return_type {
+                    add(return_type);
+                }
+            }
+        }
+    }
 }
 
 // TODO: Add a marker trait to constrain this to only type decls
@@ -174,11 +283,156 @@ impl FromPest<'_> for ResultTypeItem {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// A value that has one of the given types e.g.: `Int | String`
+pub struct UnionTypeItem {
+    pub elements: Vec<TypeElement>,
+}
+
+impl UnionTypeItem {
+    /// Type parameters used by the members of this union in order of their first appearance
+    pub fn generics(&self) -> Vec<&TypeIdent> {
+        let mut generics = vec![];
+        for element in &self.elements {
+            element.collect_generics(&mut generics);
+        }
+        generics
+    }
+}
+
+impl FromPest<'_> for UnionTypeItem {
+    type Rule = Rule;
+    type FatalError = from_pest::Void;
+
+    fn from_pest(
+        pairs: &mut pest::iterators::Pairs<'_, Self::Rule>,
+    ) -> std::result::Result<Self, ConversionError<Self::FatalError>> {
+        match pairs.next() {
+            Some(pair) if pair.as_rule() == Self::Rule::union_type => {
+                let elements = Vec::<UnionElement>::from_pest(&mut pair.into_inner())?
+                    .into_iter()
+                    .map(Into::into)
+                    .collect();
+                Ok(Self { elements })
+            }
+            Some(_) | None => Err(ConversionError::NoMatch),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// A function or closure e.g.: `(Int, mut [String]) -> Bool`
+pub struct FunctionTypeItem {
+    pub parameters: Vec<FunctionTypeParam>,
+    /// `None` for functions without a return value, these are written as `-> ()`
+    pub return_type: Option<TypeElement>,
+}
+
+impl FromPest<'_> for FunctionTypeItem {
+    type Rule = Rule;
+    type FatalError = from_pest::Void;
+
+    fn from_pest(
+        pairs: &mut pest::iterators::Pairs<'_, Self::Rule>,
+    ) -> std::result::Result<Self, ConversionError<Self::FatalError>> {
+        let pair = match pairs.next() {
+            Some(pair) if pair.as_rule() == Rule::function_type => pair,
+            Some(_) | None => return Err(ConversionError::NoMatch),
+        };
+
+        let mut pairs = pair.into_inner();
+        let parameters = Vec::<FunctionTypeParam>::from_pest(&mut pairs)?;
+        let return_type = match pairs.next() {
+            Some(pair) if pair.as_rule() == Rule::unit_type => None,
+            Some(pair) => Some(TypeElement::from_pest(
+                &mut pest::iterators::Pairs::single(pair),
+            )?),
+            None => return Err(ConversionError::NoMatch),
+        };
+
+        Ok(Self {
+            parameters,
+            return_type,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FunctionTypeParam {
+    pub decl_modifier: Option<DeclModifier>,
+    pub param_type: TypeElement,
+}
+
+impl FromPest<'_> for FunctionTypeParam {
+    type Rule = Rule;
+    type FatalError = from_pest::Void;
+
+    fn from_pest(
+        pairs: &mut pest::iterators::Pairs<'_, Self::Rule>,
+    ) -> std::result::Result<Self, ConversionError<Self::FatalError>> {
+        let pair = pairs.peek().ok_or(ConversionError::NoMatch)?;
+        let rule = pair.as_rule();
+        if rule != Rule::function_type_param && rule != Rule::single_function_type_param {
+            return Err(ConversionError::NoMatch);
+        }
+        pairs.next();
+
+        let mut pairs = pair.into_inner();
+        let decl_modifier = Option::<DeclModifier>::from_pest(&mut pairs)?;
+        let param_type = match rule {
+            Rule::function_type_param => TypeElement::from_pest(&mut pairs)?,
+            _ => SingleParamType::from_pest(&mut pairs)?.into(),
+        };
+
+        Ok(Self {
+            decl_modifier,
+            param_type,
+        })
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, FromPest)]
 #[pest_ast(rule(Rule::basic_type))]
 pub struct BasicTypeItem {
     pub ident: TypeIdent,
-    // TODO: Handle generics
+    /// Type arguments e.g.: `Container<Int>`
+    pub generics: Vec<TypeElement>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// A type parameter, these are written in lowercase e.g.: `t`
+pub struct GenericTypeItem {
+    pub ident: TypeIdent,
+}
+
+from_text!(GenericTypeItem, Rule::generic_type, |text| {
+    GenericTypeItem {
+        ident: TypeIdent::new(text),
+    }
+});
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, FromPest)]
+#[pest_ast(rule(Rule::where_clause))]
+/// Bounds on type parameters e.g.: `where t: Hash + Eq, u: Display`
+pub struct WhereClause {
+    pub bounds: Vec<WhereBound>,
+}
+
+impl WhereClause {
+    /// Traits that are required for the given type parameter
+    pub fn bounds_of<'a>(&'a self, generic: &'a TypeIdent) -> impl Iterator<Item = &'a TypeIdent> {
+        self.bounds
+            .iter()
+            .filter(move |bound| bound.generic.ident == *generic)
+            .flat_map(|bound| bound.traits.iter())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, FromPest)]
+#[pest_ast(rule(Rule::where_bound))]
+pub struct WhereBound {
+    pub generic: GenericTypeItem,
+    pub traits: Vec<TypeIdent>,
 }
 
 #[cfg(test)]
@@ -237,6 +491,94 @@ mod test {
         };
     }
 
+    #[test]
+    fn test_generic_type() {
+        let parsed: TypeElement =
+            partial_ast("t", Rule::type_item).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(parsed, TypeElement::generic(TypeIdent::new("t")));
+
+        let parsed: TypeElement =
+            partial_ast("Pair<Int, t>", Rule::type_item).unwrap_or_else(|e| panic!("{}", e));
+        let TypeElement::Plain(basic) = parsed else {
+            panic!("Expected plain type")
+        };
+        assert_eq!(basic.ident, TypeIdent::new("Pair"));
+        assert_eq!(
+            basic.generics,
+            vec![
+                TypeElement::plain(TypeIdent::new("Int")),
+                TypeElement::generic(TypeIdent::new("t"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_union_type() {
+        let parsed: TypeElement =
+            partial_ast("Int | [String]", Rule::type_item).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            parsed,
+            TypeElement::union(
+                TypeElement::plain(TypeIdent::new("Int")),
+                TypeElement::array(TypeElement::plain(TypeIdent::new("String")))
+            )
+        );
+    }
+
+    #[test]
+    fn test_function_type() {
+        let parsed: TypeElement =
+            partial_ast("t -> [u]", Rule::type_item).unwrap_or_else(|e| panic!("{}", e));
+        let TypeElement::Function(function) = parsed else {
+            panic!("Expected function type")
+        };
+        assert_eq!(
+            function.parameters,
+            vec![FunctionTypeParam {
+                decl_modifier: None,
+                param_type: TypeElement::generic(TypeIdent::new("t")),
+            }]
+        );
+        assert_eq!(
+            function.return_type,
+            Some(TypeElement::array(TypeElement::generic(TypeIdent::new(
+                "u"
+            ))))
+        );
+
+        let parsed: TypeElement = partial_ast("(Int, mut (Int, Int)) -> ()", Rule::type_item)
+            .unwrap_or_else(|e| panic!("{}", e));
+        let TypeElement::Function(function) = parsed else {
+            panic!("Expected function type")
+        };
+        assert_eq!(function.parameters.len(), 2);
+        assert!(matches!(
+            function.parameters[1].decl_modifier,
+            Some(DeclModifier::Mut(_))
+        ));
+        assert!(matches!(
+            function.parameters[1].param_type,
+            TypeElement::Tuple(_)
+        ));
+        assert_eq!(function.return_type, None);
+    }
+
+    #[test]
+    fn test_where_clause() {
+        let parsed: WhereClause = partial_ast("where t: Hash + Eq, u: Display", Rule::where_clause)
+            .unwrap_or_else(|e| panic!("{}", e));
+        let t = TypeIdent::new("t");
+        let u = TypeIdent::new("u");
+        assert_eq!(
+            parsed.bounds_of(&t).collect::<Vec<_>>(),
+            vec![&TypeIdent::new("Hash"), &TypeIdent::new("Eq")]
+        );
+        assert_eq!(
+            parsed.bounds_of(&u).collect::<Vec<_>>(),
+            vec![&TypeIdent::new("Display")]
+        );
+    }
+
     test_collection_type!("[Int]", test_array_type, array_type, Array, ArrayTypeItem);
     test_collection_type!("{Int}", test_set_type, set_type, Set, SetTypeItem);
 
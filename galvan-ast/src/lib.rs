@@ -91,6 +91,7 @@ where
 pub struct SegmentedAsts {
     pub types: Vec<ToplevelItem<TypeDecl>>,
     pub functions: Vec<ToplevelItem<FnDecl>>,
+    pub consts: Vec<ToplevelItem<ConstDecl>>,
     pub tests: Vec<ToplevelItem<TestDecl>>,
     pub main: Option<ToplevelItem<MainDecl>>,
     // pub other: Vec<ToplevelItem<CustomTaskDecl>>
@@ -104,6 +105,7 @@ impl SegmentAst for Ast {
     fn segmented(self) -> Result<SegmentedAsts> {
         let mut types = Vec::new();
         let mut functions = Vec::new();
+        let mut consts = Vec::new();
         let mut tests = Vec::new();
         let mut main = None;
 
@@ -117,6 +119,10 @@ impl SegmentAst for Ast {
                     item,
                     source: self.source.clone(),
                 }),
+                RootItem::Const(item) => consts.push(ToplevelItem {
+                    item,
+                    source: self.source.clone(),
+                }),
                 RootItem::Test(item) => tests.push(ToplevelItem {
                     item,
                     source: self.source.clone(),
@@ -137,6 +143,7 @@ impl SegmentAst for Ast {
         Ok(SegmentedAsts {
             types,
             functions,
+            consts,
             tests,
             main,
         })
@@ -147,6 +154,7 @@ impl SegmentAst for Vec<Ast> {
     fn segmented(self) -> Result<SegmentedAsts> {
         let mut types = Vec::new();
         let mut functions = Vec::new();
+        let mut consts = Vec::new();
         let mut tests = Vec::new();
         let mut main = None;
         let segmented = self.into_iter().map(SegmentAst::segmented);
@@ -155,6 +163,7 @@ impl SegmentAst for Vec<Ast> {
             let ast = ast?;
             types.extend(ast.types);
             functions.extend(ast.functions);
+            consts.extend(ast.consts);
             tests.extend(ast.tests);
             if let Some(main_decl) = ast.main {
                 if main.is_some() {
@@ -168,6 +177,7 @@ impl SegmentAst for Vec<Ast> {
         Ok(SegmentedAsts {
             types,
             functions,
+            consts,
             tests,
             main,
         })
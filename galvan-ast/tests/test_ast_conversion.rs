@@ -25,6 +25,7 @@ mod test_utils {
 
     pub fn main(statements: Vec<Statement>) -> RootItem {
         RootItem::Main(MainDecl {
+            asyncness: Async::Inherited,
             body: Body { statements },
         })
     }
@@ -38,6 +39,7 @@ mod test_utils {
             visibility,
             ident: TypeIdent::new(ident),
             members,
+            where_clause: None,
         })
     }
 
@@ -50,6 +52,7 @@ mod test_utils {
             visibility,
             ident: TypeIdent::new(ident),
             members,
+            where_clause: None,
         })
     }
 
@@ -58,6 +61,7 @@ mod test_utils {
             visibility,
             ident: TypeIdent::new(ident),
             r#type: ty,
+            where_clause: None,
         })
     }
 
@@ -68,12 +72,130 @@ mod test_utils {
         })
     }
 
+    pub fn enum_type(
+        visibility: Visibility,
+        ident: &str,
+        fields: Vec<StructTypeMember>,
+        variants: Vec<EnumTypeVariant>,
+    ) -> TypeDecl {
+        TypeDecl::Enum(EnumTypeDecl {
+            visibility,
+            ident: TypeIdent::new(ident),
+            fields,
+            where_clause: None,
+            variants,
+        })
+    }
+
+    pub fn unit_variant(ident: &str) -> EnumTypeVariant {
+        EnumTypeVariant {
+            ident: TypeIdent::new(ident),
+            fields: EnumVariantFields::Unit,
+        }
+    }
+
+    pub fn tuple_variant(ident: &str, members: Vec<TupleTypeMember>) -> EnumTypeVariant {
+        EnumTypeVariant {
+            ident: TypeIdent::new(ident),
+            fields: EnumVariantFields::Tuple(members),
+        }
+    }
+
+    pub fn struct_variant(ident: &str, members: Vec<StructTypeMember>) -> EnumTypeVariant {
+        EnumTypeVariant {
+            ident: TypeIdent::new(ident),
+            fields: EnumVariantFields::Struct(members),
+        }
+    }
+
     pub fn plain(ident: &str) -> TypeElement {
         TypeElement::plain(TypeIdent::new(ident))
     }
 
+    pub fn generic_type(ident: &str, generics: Vec<TypeElement>) -> TypeElement {
+        TypeElement::Plain(BasicTypeItem {
+            ident: TypeIdent::new(ident),
+            generics,
+        })
+    }
+
+    pub fn generic(ident: &str) -> TypeElement {
+        TypeElement::generic(TypeIdent::new(ident))
+    }
+
+    pub fn where_clause(bounds: Vec<(&str, Vec<&str>)>) -> WhereClause {
+        WhereClause {
+            bounds: bounds
+                .into_iter()
+                .map(|(generic, traits)| WhereBound {
+                    generic: GenericTypeItem {
+                        ident: TypeIdent::new(generic),
+                    },
+                    traits: traits.into_iter().map(TypeIdent::new).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn bounded_type(mut ty: TypeDecl, clause: WhereClause) -> TypeDecl {
+        match &mut ty {
+            TypeDecl::Enum(e) => e.where_clause = Some(clause),
+            TypeDecl::Tuple(t) => t.where_clause = Some(clause),
+            TypeDecl::Struct(s) => s.where_clause = Some(clause),
+            TypeDecl::Alias(a) => a.where_clause = Some(clause),
+            TypeDecl::Empty(_) => panic!("Empty types cannot have a where clause"),
+        }
+        ty
+    }
+
+    pub fn bounded_function(mut f: FnDecl, clause: WhereClause) -> FnDecl {
+        f.signature.where_clause = Some(clause);
+        f
+    }
+
+    pub fn expression_function(mut f: FnDecl) -> FnDecl {
+        f.is_expression_body = true;
+        f
+    }
+
+    pub fn annotated_function(mut f: FnDecl, annotations: Vec<Annotation>) -> FnDecl {
+        f.annotations = annotations;
+        f
+    }
+
+    pub fn annotation(name: &str, arguments: Vec<AnnotationArg>) -> Annotation {
+        Annotation {
+            identifier: Ident::new(name),
+            arguments,
+        }
+    }
+
+    pub fn string_arg(value: &str) -> AnnotationArg {
+        AnnotationArg::String(StringLiteral::from(format!("\"{value}\"")))
+    }
+
+    pub fn union(elements: Vec<TypeElement>) -> TypeElement {
+        TypeElement::Union(Box::new(UnionTypeItem { elements }))
+    }
+
+    pub fn function_type(
+        params: Vec<(Option<DeclModifier>, TypeElement)>,
+        return_type: Option<TypeElement>,
+    ) -> TypeElement {
+        TypeElement::Function(Box::new(FunctionTypeItem {
+            parameters: params
+                .into_iter()
+                .map(|(decl_modifier, param_type)| FunctionTypeParam {
+                    decl_modifier,
+                    param_type,
+                })
+                .collect(),
+            return_type,
+        }))
+    }
+
     pub fn optional(ty: TypeElement) -> TypeElement {
-        TypeElement::optional(ty.try_into().unwrap())
+        TypeElement::optional(ty)
     }
 
     pub fn result(success: TypeElement, error: Option<TypeElement>) -> TypeElement {
@@ -108,6 +230,44 @@ mod test_utils {
         TupleTypeMember { r#type: ty }
     }
 
+    pub fn async_function(
+        visibility: Visibility,
+        name: &str,
+        parameters: ParamList,
+        return_type: Option<TypeElement>,
+        block: Body,
+    ) -> FnDecl {
+        let mut decl = function(visibility, name, parameters, return_type, block);
+        decl.signature.asyncness = Async::Async;
+        decl
+    }
+
+    pub fn const_function(
+        visibility: Visibility,
+        name: &str,
+        parameters: ParamList,
+        return_type: Option<TypeElement>,
+        block: Body,
+    ) -> FnDecl {
+        let mut decl = function(visibility, name, parameters, return_type, block);
+        decl.signature.constness = Const::Const;
+        decl
+    }
+
+    pub fn constant(
+        visibility: Visibility,
+        name: &str,
+        ty: TypeElement,
+        value: Expression,
+    ) -> RootItem {
+        RootItem::Const(ConstDecl {
+            visibility,
+            identifier: Ident::new(name),
+            ty,
+            value,
+        })
+    }
+
     pub fn inherited() -> Visibility {
         Visibility::Inherited
     }
@@ -124,13 +284,18 @@ mod test_utils {
         block: Body,
     ) -> FnDecl {
         FnDecl {
+            annotations: vec![],
             signature: FnSignature {
                 visibility,
+                constness: Const::Inherited,
+                asyncness: Async::Inherited,
                 identifier: Ident::new(name),
                 parameters,
                 return_type,
+                where_clause: None,
             },
             block,
+            is_expression_body: false,
         }
     }
 
@@ -140,8 +305,32 @@ mod test_utils {
                 .into_iter()
                 .map(|(decl_modifier, name, ty)| Param {
                     decl_modifier,
+                    label: None,
                     identifier: Ident::new(name),
                     param_type: ty,
+                    default_value: None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn labeled_params(
+        params: Vec<(
+            Option<&'static str>,
+            &'static str,
+            TypeElement,
+            Option<Expression>,
+        )>,
+    ) -> ParamList {
+        ParamList {
+            params: params
+                .into_iter()
+                .map(|(label, name, ty, default_value)| Param {
+                    decl_modifier: None,
+                    label: label.map(Ident::new),
+                    identifier: Ident::new(name),
+                    param_type: ty,
+                    default_value,
                 })
                 .collect(),
         }
@@ -159,6 +348,25 @@ mod test_utils {
         SingleExpression::from(Literal::from(NumberLiteral::new(value))).into()
     }
 
+    pub fn string(value: &str) -> Expression {
+        SingleExpression::from(Literal::from(StringLiteral::from(format!("\"{value}\"")))).into()
+    }
+
+    pub fn interpolated(literal: &str, segments: Vec<StringSegment>) -> Expression {
+        SingleExpression::from(Literal::from(StringLiteral::new(literal, segments))).into()
+    }
+
+    pub fn text(text: &str) -> StringSegment {
+        StringSegment::Text(text.into())
+    }
+
+    pub fn interpolation(expression: Expression, format_spec: Option<&str>) -> StringSegment {
+        StringSegment::Interpolation(Interpolation {
+            expression,
+            format_spec: format_spec.map(Into::into),
+        })
+    }
+
     pub fn variable(ident: &str) -> Expression {
         SingleExpression::from(Ident::new(ident)).into()
     }
@@ -179,6 +387,7 @@ mod test_utils {
             arguments: arguments
                 .into_iter()
                 .map(|(modifier, expression)| FunctionCallArg {
+                    label: None,
                     modifier,
                     expression,
                 })
@@ -187,6 +396,205 @@ mod test_utils {
         .into()
     }
 
+    pub fn labeled_call(ident: &str, arguments: Vec<(Option<&str>, Expression)>) -> Expression {
+        SingleExpression::from(FunctionCall {
+            identifier: Ident::new(ident),
+            arguments: arguments
+                .into_iter()
+                .map(|(label, expression)| FunctionCallArg {
+                    label: label.map(Ident::new),
+                    modifier: None,
+                    expression,
+                })
+                .collect(),
+        })
+        .into()
+    }
+
+    pub fn index(base: Expression, index: Expression) -> Expression {
+        let Expression::SingleExpression(base) = base else {
+            panic!("Only single expressions can be indexed")
+        };
+        SingleExpression::from(Box::new(PostfixExpression::from(AccessExpression {
+            base,
+            index,
+        })))
+        .into()
+    }
+
+    pub fn slice(
+        base: Expression,
+        start: Option<Expression>,
+        end: Option<Expression>,
+    ) -> Expression {
+        let Expression::SingleExpression(base) = base else {
+            panic!("Only single expressions can be sliced")
+        };
+        SingleExpression::from(Box::new(PostfixExpression::from(SliceExpression {
+            base,
+            start,
+            end,
+        })))
+        .into()
+    }
+
+    pub fn operation(left: Expression, operator: InfixOperator, right: Expression) -> Expression {
+        fn node(expression: Expression) -> OperatorTreeNode {
+            match expression {
+                Expression::OperatorTree(tree) => Box::new(tree).into(),
+                Expression::PrefixExpression(prefix) => SimpleExpression::from(prefix).into(),
+                Expression::MemberChain(chain) => SimpleExpression::from(chain).into(),
+                Expression::SingleExpression(single) => SimpleExpression::from(single).into(),
+                _ => panic!("Only simple expressions can be operands"),
+            }
+        }
+
+        OperatorTree {
+            left: node(left),
+            operator,
+            right: node(right),
+        }
+        .into()
+    }
+
+    pub fn group(inner: Expression) -> Expression {
+        SingleExpression::from(Group(Box::new(inner.into()))).into()
+    }
+
+    pub fn block_expression(body: Body) -> Expression {
+        SingleExpression::from(Block { body }).into()
+    }
+
+    pub fn match_expression(subject: Expression, arms: Vec<MatchArm>) -> Expression {
+        MatchExpression {
+            subject: Box::new(subject),
+            arms,
+        }
+        .into()
+    }
+
+    pub fn match_arm(pattern: Pattern, guard: Option<Expression>, body: Expression) -> MatchArm {
+        MatchArm {
+            pattern,
+            guard,
+            body: Block {
+                body: Body {
+                    statements: vec![TopExpression::from(body).into()],
+                },
+            },
+        }
+    }
+
+    pub fn range(start: Expression, kind: RangeKind, end: Expression) -> Expression {
+        RangeExpression {
+            start: Box::new(start),
+            kind,
+            end: Box::new(end),
+        }
+        .into()
+    }
+
+    pub fn for_loop(
+        iterable: Expression,
+        arguments: Vec<ClosureArgument>,
+        body: Body,
+    ) -> Statement {
+        ForLoop {
+            label: None,
+            iterable,
+            arguments,
+            body: Block { body },
+        }
+        .into()
+    }
+
+    pub fn label(ident: Option<&str>) -> Option<Label> {
+        ident.map(|ident| Label {
+            ident: Ident::new(ident),
+        })
+    }
+
+    pub fn loop_expression(label: Option<&str>, body: Body) -> Expression {
+        LoopExpression {
+            label: self::label(label),
+            body: Block { body },
+        }
+        .into()
+    }
+
+    pub fn while_loop(label: Option<&str>, condition: Expression, body: Body) -> Statement {
+        WhileLoop {
+            label: self::label(label),
+            condition,
+            body: Block { body },
+        }
+        .into()
+    }
+
+    pub fn break_statement(label: Option<&str>, value: Option<Expression>) -> Statement {
+        Break {
+            label: self::label(label),
+            value,
+        }
+        .into()
+    }
+
+    pub fn continue_statement(label: Option<&str>) -> Statement {
+        Continue {
+            label: self::label(label),
+        }
+        .into()
+    }
+
+    pub fn return_expression(value: Option<Expression>) -> Expression {
+        Return {
+            value: value.map(Box::new),
+        }
+        .into()
+    }
+
+    pub fn throw_expression(value: Expression) -> Expression {
+        Throw {
+            value: Box::new(value),
+        }
+        .into()
+    }
+
+    pub fn closure_argument(ident: &str) -> ClosureArgument {
+        ClosureArgument {
+            ident: Ident::new(ident),
+            ty: None,
+        }
+    }
+
+    pub fn trailing_closure(arguments: Vec<ClosureArgument>, body: Body) -> Expression {
+        Closure {
+            arguments,
+            block: Block { body },
+        }
+        .into()
+    }
+
+    pub fn else_expression(
+        receiver: Expression,
+        error: Option<ClosureArgument>,
+        body: Body,
+    ) -> TopExpression {
+        let Expression::SingleExpression(receiver) = receiver else {
+            panic!("Receiver of an else expression must be a single expression")
+        };
+        ElseExpression {
+            receiver: Box::new(receiver),
+            error,
+            block: Block { body },
+        }
+        .into()
+    }
+
+    pub fn number_pattern(value: &str) -> Pattern {
+        Pattern::Literal(NumberLiteral::new(value).into())
+    }
+
     pub fn decl(
         modifier: DeclModifier,
         ident: &str,
@@ -11,7 +11,7 @@ use pest_derive::*;
 #[grammar = "galvan.pest"]
 pub struct GalvanParser;
 
-pub struct Span(usize, usize);
+pub struct Span(pub usize, pub usize);
 pub type BorrowedSpan<'a> = pest::Span<'a>;
 
 impl From<pest::Span<'_>> for Span {
@@ -1,8 +1,11 @@
-use galvan_ast::{FnDecl, Ident, MainDecl, SegmentedAsts, ToplevelItem, TypeDecl, TypeIdent};
+use galvan_ast::{
+    AnnotationArg, ConstDecl, DeclModifier, FnDecl, FnSignature, Ident, MainDecl, SegmentedAsts,
+    ToplevelItem, TypeDecl, TypeElement, TypeIdent,
+};
 use std::collections::HashMap;
 use thiserror::Error;
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct LookupContext<'a> {
     /// Types are resolved by their name
     pub types: HashMap<TypeIdent, &'a ToplevelItem<TypeDecl>>,
@@ -12,6 +15,12 @@ pub struct LookupContext<'a> {
     /// `fn foo(bar a: i32, b: i32) -> i32` is identified as `foo:bar`
     /// `fn foo(self: i32, b: i32) -> i32` is identified as `i32::foo`
     pub functions: HashMap<FunctionId, &'a ToplevelItem<FnDecl>>,
+    /// Functions annotated with `@infix("⨁")` are resolved by their operator symbol
+    pub infix_operators: HashMap<Box<str>, &'a ToplevelItem<FnDecl>>,
+    /// Functions annotated with `@prefix("√")` are resolved by their operator symbol
+    pub prefix_operators: HashMap<Box<str>, &'a ToplevelItem<FnDecl>>,
+    /// Constants are resolved by their name, they share their namespace with functions
+    pub consts: HashMap<Ident, &'a ToplevelItem<ConstDecl>>,
     // TODO: Nested contexts for resolving names from imported modules
     // pub imports: HashMap<String, LookupContext<'a>>,
     pub main: Option<&'a ToplevelItem<MainDecl>>,
@@ -22,10 +31,16 @@ pub trait Lookup {
 
     fn resolve_function(
         &self,
-        receiver: Option<&TypeIdent>,
+        receiver: Option<&TypeElement>,
         name: &Ident,
         labels: &[&str],
     ) -> Option<&ToplevelItem<FnDecl>>;
+
+    fn resolve_infix_operator(&self, symbol: &str) -> Option<&ToplevelItem<FnDecl>>;
+
+    fn resolve_prefix_operator(&self, symbol: &str) -> Option<&ToplevelItem<FnDecl>>;
+
+    fn resolve_const(&self, name: &Ident) -> Option<&ToplevelItem<ConstDecl>>;
 }
 
 // TODO: Include spans in errors
@@ -39,6 +54,12 @@ pub enum LookupError {
     DuplicateType(TypeIdent),
     #[error("Duplicate function")]
     DuplicateFunction,
+    #[error("Duplicate operator")]
+    DuplicateOperator(Box<str>),
+    #[error("Duplicate constant")]
+    DuplicateConst(Ident),
+    #[error("Invalid operator declaration")]
+    InvalidOperator(Ident),
 }
 
 impl<'a> LookupContext<'a> {
@@ -47,10 +68,39 @@ impl<'a> LookupContext<'a> {
     }
     pub fn add_from(&mut self, asts: &'a SegmentedAsts) -> Result<(), LookupError> {
         for func in &asts.functions {
-            let func_id = FunctionId::new(None, &func.signature.identifier, &[]);
+            let receiver = func
+                .signature
+                .receiver()
+                .map(|receiver| receiver_ident(&receiver.param_type));
+            let func_id = FunctionId::new(
+                receiver.as_ref(),
+                &func.signature.identifier,
+                &func.signature.labels(),
+            );
             if self.functions.insert(func_id, func).is_some() {
                 return Err(LookupError::DuplicateFunction);
             }
+
+            if let Some(symbol) = operator_symbol(func, "infix", 2)? {
+                if self.infix_operators.insert(symbol.into(), func).is_some() {
+                    return Err(LookupError::DuplicateOperator(symbol.into()));
+                }
+            }
+            if let Some(symbol) = operator_symbol(func, "prefix", 1)? {
+                if self.prefix_operators.insert(symbol.into(), func).is_some() {
+                    return Err(LookupError::DuplicateOperator(symbol.into()));
+                }
+            }
+        }
+
+        for constant in &asts.consts {
+            let name = &constant.identifier;
+            let is_function = self
+                .functions
+                .contains_key(&FunctionId::new(None, name, &[]));
+            if is_function || self.consts.insert(name.clone(), constant).is_some() {
+                return Err(LookupError::DuplicateConst(name.clone()));
+            }
         }
 
         for type_decl in &asts.types {
@@ -74,21 +124,217 @@ impl<'a> LookupContext<'a> {
 
 impl Lookup for LookupContext<'_> {
     fn resolve_type(&self, name: &TypeIdent) -> Option<&ToplevelItem<TypeDecl>> {
-        self.types.get(&name).copied()
+        self.types.get(name).copied()
     }
 
     fn resolve_function(
         &self,
-        receiver: Option<&TypeIdent>,
+        receiver: Option<&TypeElement>,
         name: &Ident,
         labels: &[&str],
     ) -> Option<&ToplevelItem<FnDecl>> {
-        let func_id = FunctionId::new(receiver, name, labels);
-        self.functions.get(&func_id).copied()
+        let receiver_id = receiver.map(receiver_ident);
+        let func_id = FunctionId::new(receiver_id.as_ref(), name, labels);
+        if let Some(func) = self.functions.get(&func_id) {
+            return Some(func);
+        }
+
+        // Functions for generic receivers and labeled parameters with a default value are only resolved if unambiguous
+        let mut candidates = self
+            .functions
+            .values()
+            .filter(|func| {
+                let signature = &func.signature;
+                let declared = signature.receiver().map(|receiver| &receiver.param_type);
+                let accepts_receiver = match (declared, receiver) {
+                    (Some(declared), Some(receiver)) => accepts_receiver(declared, receiver),
+                    (None, None) => true,
+                    _ => false,
+                };
+                signature.identifier == *name
+                    && accepts_receiver
+                    && accepts_labels(signature, labels)
+            })
+            .copied();
+        match (candidates.next(), candidates.next()) {
+            (Some(func), None) => Some(func),
+            _ => None,
+        }
     }
+
+    fn resolve_infix_operator(&self, symbol: &str) -> Option<&ToplevelItem<FnDecl>> {
+        self.infix_operators.get(symbol).copied()
+    }
+
+    fn resolve_prefix_operator(&self, symbol: &str) -> Option<&ToplevelItem<FnDecl>> {
+        self.prefix_operators.get(symbol).copied()
+    }
+
+    fn resolve_const(&self, name: &Ident) -> Option<&ToplevelItem<ConstDecl>> {
+        self.consts.get(name).copied()
+    }
+}
+
+/// Name of the receiver type under which member functions are registered
+///
+/// Type parameters are named `_`, so `fn first(self: [t])` is registered as `[_]` and resolved for every array
+/// that has no function with the same name for its element type e.g.: `fn first(self: [Int])`
+pub fn receiver_ident(receiver: &TypeElement) -> TypeIdent {
+    TypeIdent::new(receiver_name(receiver))
+}
+
+fn receiver_name(ty: &TypeElement) -> String {
+    let names = |types: &[TypeElement]| types.iter().map(receiver_name).collect::<Vec<_>>();
+    match ty {
+        TypeElement::Plain(plain) if plain.generics.is_empty() => plain.ident.to_string(),
+        TypeElement::Plain(plain) => {
+            format!("{}<{}>", plain.ident, names(&plain.generics).join(", "))
+        }
+        TypeElement::Generic(_) => "_".to_owned(),
+        TypeElement::Array(array) => format!("[{}]", receiver_name(&array.elements)),
+        TypeElement::Dictionary(dict) => {
+            format!(
+                "{{{}: {}}}",
+                receiver_name(&dict.key),
+                receiver_name(&dict.value)
+            )
+        }
+        TypeElement::OrderedDictionary(dict) => {
+            format!(
+                "[{}: {}]",
+                receiver_name(&dict.key),
+                receiver_name(&dict.value)
+            )
+        }
+        TypeElement::Set(set) => format!("{{{}}}", receiver_name(&set.elements)),
+        TypeElement::Tuple(tuple) => format!("({})", names(&tuple.elements).join(", ")),
+        TypeElement::Optional(optional) => format!("{}?", receiver_name(&optional.some)),
+        TypeElement::Result(result) => format!(
+            "{}!{}",
+            receiver_name(&result.success),
+            result.error.as_ref().map_or(String::new(), receiver_name)
+        ),
+        TypeElement::Union(union) => names(&union.elements).join(" | "),
+        TypeElement::Function(function) => format!(
+            "({}) -> {}",
+            function
+                .parameters
+                .iter()
+                .map(|param| receiver_name(&param.param_type))
+                .collect::<Vec<_>>()
+                .join(", "),
+            function
+                .return_type
+                .as_ref()
+                .map_or("()".to_owned(), receiver_name)
+        ),
+    }
+}
+
+/// Checks if a member function that is declared for the receiver type `declared` can be called on `receiver`
+///
+/// Type parameters of the declaration accept any type, as do placeholders of types that are not fully inferred
+fn accepts_receiver(declared: &TypeElement, receiver: &TypeElement) -> bool {
+    let all = |declared: &[TypeElement], receiver: &[TypeElement]| {
+        declared.len() == receiver.len()
+            && declared
+                .iter()
+                .zip(receiver)
+                .all(|(declared, receiver)| accepts_receiver(declared, receiver))
+    };
+    match (declared, receiver) {
+        (TypeElement::Generic(_), _) => true,
+        (_, TypeElement::Plain(plain)) if plain.ident.as_str().starts_with("__") => true,
+        (TypeElement::Plain(declared), TypeElement::Plain(receiver)) => {
+            declared.ident == receiver.ident
+                && (receiver.generics.is_empty() || all(&declared.generics, &receiver.generics))
+        }
+        (TypeElement::Array(declared), TypeElement::Array(receiver)) => {
+            accepts_receiver(&declared.elements, &receiver.elements)
+        }
+        (TypeElement::Set(declared), TypeElement::Set(receiver)) => {
+            accepts_receiver(&declared.elements, &receiver.elements)
+        }
+        (TypeElement::Dictionary(declared), TypeElement::Dictionary(receiver)) => {
+            accepts_receiver(&declared.key, &receiver.key)
+                && accepts_receiver(&declared.value, &receiver.value)
+        }
+        (TypeElement::OrderedDictionary(declared), TypeElement::OrderedDictionary(receiver)) => {
+            accepts_receiver(&declared.key, &receiver.key)
+                && accepts_receiver(&declared.value, &receiver.value)
+        }
+        (TypeElement::Tuple(declared), TypeElement::Tuple(receiver)) => {
+            all(&declared.elements, &receiver.elements)
+        }
+        (TypeElement::Optional(declared), TypeElement::Optional(receiver)) => {
+            accepts_receiver(&declared.some, &receiver.some)
+        }
+        (TypeElement::Result(declared), TypeElement::Result(receiver)) => {
+            accepts_receiver(&declared.success, &receiver.success)
+                && match (&declared.error, &receiver.error) {
+                    (Some(declared), Some(receiver)) => accepts_receiver(declared, receiver),
+                    (None, None) => true,
+                    _ => false,
+                }
+        }
+        (TypeElement::Union(declared), TypeElement::Union(receiver)) => {
+            all(&declared.elements, &receiver.elements)
+        }
+        (declared, receiver) => declared == receiver,
+    }
+}
+
+/// Checks if a call with the given labels can omit the remaining labeled parameters of the function
+///
+/// The labels of a call have to appear in the same order as in the declaration
+fn accepts_labels(signature: &FnSignature, labels: &[&str]) -> bool {
+    let mut labels = labels.iter().peekable();
+    for param in &signature.parameters.params {
+        let Some(label) = &param.label else {
+            continue;
+        };
+        if labels.peek() == Some(&&label.as_str()) {
+            labels.next();
+        } else if param.default_value.is_none() {
+            return false;
+        }
+    }
+    labels.next().is_none()
+}
+
+/// Symbol of a custom operator declared with the given annotation e.g.: `@infix("⨁")`
+///
+/// Operator functions take their operands as plain value parameters
+fn operator_symbol<'a>(
+    func: &'a FnDecl,
+    annotation: &str,
+    operands: usize,
+) -> Result<Option<&'a str>, LookupError> {
+    let Some(annotation) = func.annotation(annotation) else {
+        return Ok(None);
+    };
+
+    let invalid = || LookupError::InvalidOperator(func.signature.identifier.clone());
+    let [AnnotationArg::String(symbol)] = annotation.arguments.as_slice() else {
+        return Err(invalid());
+    };
+    let symbol = symbol.value();
+    let is_symbol = !symbol.is_empty()
+        && symbol
+            .chars()
+            .all(|c| !c.is_ascii() && !c.is_alphanumeric() && !c.is_whitespace());
+    let params = &func.signature.parameters.params;
+    let value_params = params
+        .iter()
+        .all(|param| matches!(param.decl_modifier, None | Some(DeclModifier::Let(_))));
+    if !is_symbol || params.len() != operands || !value_params {
+        return Err(invalid());
+    }
+
+    Ok(Some(symbol))
 }
 
-#[derive(Debug, Hash, PartialEq, Eq)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct FunctionId(Box<str>);
 
 impl FunctionId {
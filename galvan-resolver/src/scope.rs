@@ -1,27 +1,95 @@
 use crate::{Lookup, LookupContext};
 use galvan_ast::{
-    DeclModifier, FnDecl, Ident, Ownership, ToplevelItem, TypeDecl, TypeElement, TypeIdent,
+    ConstDecl, DeclModifier, FnDecl, Ident, Label, Ownership, ToplevelItem, TypeDecl, TypeElement,
+    TypeIdent,
 };
+use std::cell::RefCell;
 use std::collections::HashMap;
 
 #[derive(Debug, Default)]
 pub struct Scope<'a> {
     pub parent: Option<&'a Scope<'a>>,
     pub variables: HashMap<Ident, Variable>,
+    /// Set if this scope is the body of a loop
+    pub loop_context: Option<LoopContext>,
+    /// Set if this scope is the body of a function or closure
+    pub function_context: Option<FunctionContext>,
+    /// Set if this scope is the body of a closure without parameter list, collects the implicit parameters it uses
+    pub implicit_parameters: Option<RefCell<Vec<Ident>>>,
 
     lookup: Option<LookupContext<'a>>,
 }
 
 impl Scope<'_> {
-    pub fn child(parent: &Self) -> Scope {
+    pub fn child(parent: &Self) -> Scope<'_> {
         Scope {
             parent: Some(parent),
             variables: HashMap::new(),
+            loop_context: None,
+            function_context: None,
+            implicit_parameters: None,
             lookup: None,
         }
     }
 
+    pub fn loop_body(parent: &Self, context: LoopContext) -> Scope<'_> {
+        Scope {
+            loop_context: Some(context),
+            ..Scope::child(parent)
+        }
+    }
+
+    pub fn function_body(parent: &Self, context: FunctionContext) -> Scope<'_> {
+        Scope {
+            function_context: Some(context),
+            ..Scope::child(parent)
+        }
+    }
+
+    /// Body of a closure without parameter list, which records the implicit parameters that it uses
+    pub fn implicit_closure_body(parent: &Self, context: FunctionContext) -> Scope<'_> {
+        Scope {
+            implicit_parameters: Some(RefCell::default()),
+            ..Scope::function_body(parent, context)
+        }
+    }
+
+    /// Finds the innermost loop or the loop with the given label
+    ///
+    /// Loops outside of the surrounding function or closure cannot be exited from its body
+    pub fn get_loop(&self, label: Option<&Label>) -> Option<&LoopContext> {
+        self.loop_context
+            .as_ref()
+            .filter(|context| label.is_none() || context.label.as_ref() == label)
+            .or_else(|| {
+                if self.function_context.is_some() {
+                    None
+                } else {
+                    self.parent.and_then(|parent| parent.get_loop(label))
+                }
+            })
+    }
+
+    /// Finds the innermost function or closure, this is `None` in `main` and in tests unless they are async
+    pub fn get_function(&self) -> Option<&FunctionContext> {
+        self.function_context
+            .as_ref()
+            .or_else(|| self.parent.and_then(|parent| parent.get_function()))
+    }
+
+    /// Declares a local variable, constants cannot be shadowed as they are matched as patterns in Rust
     pub fn declare_variable(&mut self, variable: Variable) {
+        if self.resolve_const(&variable.ident).is_some() {
+            todo!(
+                "TRANSPILER ERROR: `{}` is a constant and cannot be redeclared",
+                variable.ident
+            )
+        }
+        self.variables.insert(variable.ident.clone(), variable);
+    }
+
+    /// Declares a constant as an immutable variable that is visible in all child scopes
+    pub fn declare_const(&mut self, variable: Variable) {
         self.variables.insert(variable.ident.clone(), variable);
     }
 
@@ -33,6 +101,28 @@ impl Scope<'_> {
 }
 
 impl<'a> Scope<'a> {
+    /// Records a use of `it` or `#0`, `#1`, ... in the innermost closure without parameter list
+    ///
+    /// Names that are declared inside of that closure, e.g. by an unnamed `for` loop, are not recorded
+    pub fn use_implicit_parameter(&self, ident: &Ident) {
+        if self.variables.contains_key(ident) {
+            return;
+        }
+        match (&self.implicit_parameters, self.parent) {
+            (Some(parameters), _) => parameters.borrow_mut().push(ident.clone()),
+            (None, Some(parent)) => parent.use_implicit_parameter(ident),
+            (None, None) => {}
+        }
+    }
+
+    /// The outermost scope, which holds the lookup and all constants
+    pub fn root(&self) -> &Self {
+        match self.parent {
+            Some(parent) => parent.root(),
+            None => self,
+        }
+    }
+
     pub fn set_lookup(&mut self, lookup: LookupContext<'a>) {
         self.lookup = Some(lookup);
     }
@@ -48,7 +138,7 @@ impl Lookup for Scope<'_> {
 
     fn resolve_function(
         &self,
-        receiver: Option<&TypeIdent>,
+        receiver: Option<&TypeElement>,
         name: &Ident,
         labels: &[&str],
     ) -> Option<&ToplevelItem<FnDecl>> {
@@ -60,6 +150,33 @@ impl Lookup for Scope<'_> {
                     .and_then(|parent| parent.resolve_function(receiver, name, labels))
             })
     }
+
+    fn resolve_infix_operator(&self, symbol: &str) -> Option<&ToplevelItem<FnDecl>> {
+        self.lookup
+            .as_ref()
+            .and_then(|lookup| lookup.resolve_infix_operator(symbol))
+            .or_else(|| {
+                self.parent
+                    .and_then(|parent| parent.resolve_infix_operator(symbol))
+            })
+    }
+
+    fn resolve_prefix_operator(&self, symbol: &str) -> Option<&ToplevelItem<FnDecl>> {
+        self.lookup
+            .as_ref()
+            .and_then(|lookup| lookup.resolve_prefix_operator(symbol))
+            .or_else(|| {
+                self.parent
+                    .and_then(|parent| parent.resolve_prefix_operator(symbol))
+            })
+    }
+
+    fn resolve_const(&self, name: &Ident) -> Option<&ToplevelItem<ConstDecl>> {
+        self.lookup
+            .as_ref()
+            .and_then(|lookup| lookup.resolve_const(name))
+            .or_else(|| self.parent.and_then(|parent| parent.resolve_const(name)))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -76,3 +193,20 @@ impl Variable {
         matches!(self.modifier, DeclModifier::Mut(_))
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct LoopContext {
+    pub label: Option<Label>,
+    /// Only `loop` can be exited with a value, `for` and `while` loops might not run at all
+    pub yields_value: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct FunctionContext {
+    /// The declared return type, this is `None` for closures and functions that return nothing
+    pub return_type: Option<TypeElement>,
+    /// Async functions can call other async functions, closures are never async
+    pub is_async: bool,
+    /// Const functions can only call other const functions
+    pub is_const: bool,
+}
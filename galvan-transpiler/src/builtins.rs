@@ -31,6 +31,13 @@ pub fn builtins() -> Mapping {
 
         ("String" => "String", "str"),
         ("Char" => "char", copy),
+
+        (trait "Eq" => "::std::cmp::Eq"),
+        (trait "Ord" => "::std::cmp::Ord"),
+        (trait "Hash" => "::std::hash::Hash"),
+        (trait "Default" => "::std::default::Default"),
+        (trait "Display" => "::std::fmt::Display"),
+        (trait "Debug" => "::std::fmt::Debug"),
     )
 }
 
@@ -1,12 +1,16 @@
 use crate::mapping::Mapping;
-use galvan_ast::{EmptyTypeDecl, SegmentedAsts, ToplevelItem, TypeDecl, Visibility};
+use galvan_ast::{EmptyTypeDecl, SegmentedAsts, ToplevelItem, TypeDecl, UnionTypeItem, Visibility};
 use galvan_files::Source;
 use galvan_resolver::{LookupContext, LookupError};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Default)]
 pub struct Context<'a> {
     pub lookup: LookupContext<'a>,
     pub mapping: Mapping,
+    /// Union types that were used so far, an anonymous enum is generated for each of them
+    pub(crate) unions: RefCell<BTreeMap<String, UnionTypeItem>>,
     // pub scope: Scope,
 }
 
@@ -15,6 +19,7 @@ impl<'a> Context<'a> {
         Self {
             lookup: LookupContext::default(),
             mapping,
+            unions: RefCell::default(),
         }
     }
 
@@ -37,11 +42,13 @@ pub fn predefined_from(mapping: &Mapping) -> SegmentedAsts {
         })
         .collect();
     let functions = vec![];
+    let consts = vec![];
     let tests = vec![];
     let main = None;
     SegmentedAsts {
         types,
         functions,
+        consts,
         tests,
         main,
     }
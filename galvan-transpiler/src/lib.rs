@@ -2,14 +2,15 @@ use convert_case::{Case, Casing};
 use derive_more::{Deref, Display, From};
 use galvan_ast::*;
 use galvan_files::{FileError, Source};
-use galvan_resolver::{LookupError, Scope};
+use galvan_resolver::{FunctionContext, LookupError, Scope, Variable};
 use itertools::Itertools;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::iter;
 use thiserror::Error;
 
-static SUPPRESS_WARNINGS: &str = "#![allow(warnings, unused)]";
+// The transpiled code is not written by hand, so lints like `clippy::never_loop` are suppressed as well
+static SUPPRESS_WARNINGS: &str = "#![allow(warnings, unused, clippy::all)]";
 
 // TODO: Maybe use something like https://crates.io/crates/ruast to generate the Rust code in a more reliable way
 
@@ -56,7 +57,23 @@ fn transpile_asts(asts: Vec<Ast>) -> Result<Vec<TranspileOutput>, TranspileError
     let builtins = builtins();
     let predefined = predefined_from(&builtins);
     let lookup = Context::new(builtins).with(&predefined)?.with(&segmented)?;
+    // Type inference resolves the declarations of types through the scope
     let mut scope = Scope::default();
+    scope.set_lookup(lookup.lookup.clone());
+    // Constants are visible as immutable variables in every scope
+    for constant in &segmented.consts {
+        scope.declare_const(Variable {
+            ident: constant.identifier.clone(),
+            modifier: DeclModifier::Let(LetKeyword),
+            ty: Some(constant.ty.clone()),
+            ownership: match &constant.ty {
+                TypeElement::Plain(plain) if lookup.mapping.is_copy(&plain.ident) => {
+                    Ownership::Copy
+                }
+                _ => Ownership::Owned,
+            },
+        });
+    }
 
     transpile_segmented(&segmented, &lookup, &mut scope)
 }
@@ -149,6 +166,12 @@ fn transpile_segmented(
         .join("\n\n");
     let toplevel_functions = toplevel_functions.trim();
 
+    let consts = segmented
+        .consts
+        .iter()
+        .map(|constant| constant.transpile(ctx, scope))
+        .join("\n");
+
     let tests = transpile_tests(segmented, ctx, scope);
 
     let modules = type_files
@@ -163,20 +186,12 @@ fn transpile_segmented(
     let main = segmented
         .main
         .as_ref()
-        .map(|main| transpile!(ctx, scope, "pub(crate) fn __main__() {}", main.body))
+        .map(|main| {
+            let body = transpile_entry_body(ctx, scope, main.asyncness, &main.body);
+            format!("pub(crate) fn __main__() {body}")
+        })
         .unwrap_or_default();
 
-    let lib = TranspileOutput {
-        file_name: galvan_module!("rs").into(),
-        content: format!(
-            "extern crate galvan; #[allow(unused_imports)] pub(crate) use ::galvan::std::*;\n pub(crate) mod {} {{\n{}\nuse crate::*;\n{}\n}}",
-            galvan_module!(),
-            SUPPRESS_WARNINGS,
-            [modules, toplevel_functions, &main, &tests].join("\n\n")
-        )
-        .into(),
-    };
-
     let type_files = type_files
         .iter()
         .map(|(k, v)| TranspileOutput {
@@ -184,7 +199,7 @@ fn transpile_segmented(
             content: [
                 "use crate::*;",
                 &v.ty.transpile(ctx, scope),
-                &transpile_member_functions(v.ty.ident(), &v.fns, ctx, scope),
+                &transpile_member_functions(v.ty, &v.fns, ctx, scope),
             ]
             .join("\n\n")
             .trim()
@@ -192,16 +207,32 @@ fn transpile_segmented(
         })
         .collect_vec();
 
-    let extension_files = extensions.iter().map(|(k, v)| TranspileOutput {
-        file_name: format!("{k}.rs").into(),
-        content: [
-            "use crate::*;",
-            &transpile_extension_functions(v.elem, &v.fns, ctx, scope),
-        ]
-        .join("\n\n")
-        .trim()
+    let extension_files = extensions
+        .iter()
+        .map(|(k, v)| TranspileOutput {
+            file_name: format!("{k}.rs").into(),
+            content: [
+                "use crate::*;",
+                &transpile_extension_functions(v.elem, &v.fns, ctx, scope),
+            ]
+            .join("\n\n")
+            .trim()
+            .into(),
+        })
+        .collect_vec();
+
+    // Unions are collected while transpiling, so they are emitted last
+    let unions = transpile_union_decls(ctx, scope);
+    let lib = TranspileOutput {
+        file_name: galvan_module!("rs").into(),
+        content: format!(
+            "extern crate galvan; #[allow(unused_imports)] pub(crate) use ::galvan::std::*;\n pub(crate) mod {} {{\n{}\nuse crate::*;\n{}\n}}",
+            galvan_module!(),
+            SUPPRESS_WARNINGS,
+            [modules, &unions, &consts, toplevel_functions, &main, &tests].join("\n\n")
+        )
         .into(),
-    });
+    };
 
     Ok(type_files
         .into_iter()
@@ -272,8 +303,31 @@ fn transpile_tests(segmented_asts: &SegmentedAsts, ctx: &Context, scope: &mut Sc
     test_mod
 }
 
+/// Transpiles the body of `main` or a test
+///
+/// Async bodies are run to completion by the runtime that the `async` feature of the `galvan` crate provides
+pub(crate) fn transpile_entry_body(
+    ctx: &Context,
+    scope: &mut Scope,
+    asyncness: Async,
+    body: &Body,
+) -> String {
+    if !asyncness.is_async() {
+        return body.transpile(ctx, scope);
+    }
+
+    let context = FunctionContext {
+        return_type: None,
+        is_async: true,
+        is_const: false,
+    };
+    let mut body_scope = Scope::function_body(scope, context);
+    let body = transpile_body(ctx, &mut body_scope, body, BodyResult::Discarded);
+    format!("{{ ::galvan::runtime::block_on(async {body}) }}")
+}
+
 fn transpile_member_functions(
-    ty: &TypeIdent,
+    ty: &TypeDecl,
     fns: &[&FnDecl],
     ctx: &Context,
     scope: &mut Scope,
@@ -282,12 +336,64 @@ fn transpile_member_functions(
         return "".into();
     }
 
-    let transpiled_fns = fns
-        .iter()
-        .map(|f| f.transpile(ctx, scope))
+    if ty.generics().is_empty() {
+        let transpiled_fns = fns
+            .iter()
+            .map(|f| f.transpile(ctx, scope))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        return transpile!(ctx, scope, "impl {} {{\n{transpiled_fns}\n}}", ty.ident());
+    }
+
+    // Receivers can name the type parameters of a generic type differently, so each function gets its own impl block
+    fns.iter()
+        .map(|f| {
+            let receiver = &f
+                .signature
+                .receiver()
+                .expect("Member functions should have a receiver")
+                .param_type;
+            if receiver.generics().is_empty() {
+                todo!(
+                    "TRANSPILER ERROR: Receiver of {} needs type arguments for generic type {}",
+                    f.signature.identifier,
+                    ty.ident()
+                )
+            }
+            // Bounds of the function itself are declared on the function
+            let bounds = where_clause(ctx, &receiver.generics(), [&receiver_bounds(ty, receiver)]);
+            let generics = generic_params(&receiver.generics());
+            let transpiled_fn = f.transpile(ctx, scope);
+            transpile!(
+                ctx,
+                scope,
+                "impl{generics} {}{bounds} {{\n{transpiled_fn}\n}}",
+                receiver
+            )
+        })
         .collect::<Vec<_>>()
-        .join("\n\n");
-    transpile!(ctx, scope, "impl {} {{\n{transpiled_fns}\n}}", ty)
+        .join("\n\n")
+}
+
+/// Bounds of a generic type declaration, applied to the type parameters that the receiver uses as type arguments
+fn receiver_bounds(ty: &TypeDecl, receiver: &TypeElement) -> WhereClause {
+    let (Some(clause), TypeElement::Plain(receiver)) = (ty.where_clause(), receiver) else {
+        return WhereClause::default();
+    };
+
+    let bounds = ty
+        .generics()
+        .into_iter()
+        .zip(&receiver.generics)
+        .filter_map(|(param, arg)| match arg {
+            TypeElement::Generic(generic) => Some(WhereBound {
+                generic: generic.clone(),
+                traits: clause.bounds_of(param).cloned().collect(),
+            }),
+            _ => None,
+        })
+        .collect();
+    WhereClause { bounds }
 }
 
 fn transpile_extension_functions(
@@ -304,32 +410,34 @@ fn transpile_extension_functions(
     {
         todo!("TRANSPILER ERROR: Member functions for types declared outside of galvan module must have default visibility!");
     }
+    // Extension functions are declared in a trait, which cannot contain const functions
+    if let Some(f) = fns.iter().find(|f| f.signature.constness.is_const()) {
+        todo!(
+            "TRANSPILER ERROR: The member function `{}` cannot be const, as it extends a type declared outside of galvan module",
+            f.signature.identifier
+        );
+    }
 
-    let trait_name = extension_name(&ty);
-    let fn_signatures = fns
+    let trait_name = extension_name(ty);
+    let generic_params = generic_params(&ty.generics());
+    let generic_args = generic_args(&ty.generics());
+    // Bounds on the type parameters of the receiver are declared on each function, so they only apply to that function
+    let (fn_signatures, transpiled_fns): (Vec<_>, Vec<_>) =
+        fns.iter().map(|f| transpile_fn(ctx, scope, f)).unzip();
+    let fn_signatures = fn_signatures.join(";\n") + ";";
+    let transpiled_fns = transpiled_fns
         .iter()
-        .map(|f| FnSignature {
-            visibility: Visibility::Private,
-            ..f.signature.clone()
-        })
-        .map(|s| s.transpile(ctx, scope))
-        .collect::<Vec<_>>()
-        .join(";\n")
-        + ";";
-    let transpiled_fns = fns
-        .iter()
-        .map(|f| f.transpile(ctx, scope))
         .map(|s| s.strip_prefix("pub(crate) ").unwrap().to_owned())
         .collect::<Vec<_>>()
         .join("\n\n");
 
     transpile! {ctx, scope,
         "
-        pub trait {trait_name} {{
+        pub trait {trait_name}{generic_params} {{
             {fn_signatures}
         }}
 
-        impl {trait_name} for {} {{
+        impl{generic_params} {trait_name}{generic_args} for {} {{
             {transpiled_fns}
         }}
         ", ty
@@ -337,36 +445,6 @@ fn transpile_extension_functions(
 }
 
 fn extension_name(ty: &TypeElement) -> String {
-    fn escaped_name(ty: &TypeElement) -> String {
-        match ty {
-            TypeElement::Plain(ty) => ty.ident.as_str().to_case(Case::UpperCamel),
-            TypeElement::Tuple(ty) => format!(
-                "Tuple_{}",
-                ty.elements
-                    .iter()
-                    .map(escaped_name)
-                    .collect::<Vec<_>>()
-                    .join("_")
-            ),
-            TypeElement::Result(ty) => format!(
-                "Result_{}_{}",
-                escaped_name(&ty.success),
-                ty.error.as_ref().map_or("".into(), escaped_name)
-            ),
-            TypeElement::Optional(ty) => format!("Option_{}_Ext", escaped_name(&ty.some)),
-            TypeElement::Dictionary(ty) => {
-                format!("Dict_{}_{}", escaped_name(&ty.key), escaped_name(&ty.value))
-            }
-            TypeElement::OrderedDictionary(ty) => format!(
-                "OrderedDict_{}_{}",
-                escaped_name(&ty.key),
-                escaped_name(&ty.value)
-            ),
-            TypeElement::Array(ty) => format!("Array_{}", escaped_name(&ty.elements)),
-            TypeElement::Set(ty) => format!("Set_{}", escaped_name(&ty.elements)),
-        }
-    }
-
     escaped_name(ty) + "_Ext"
 }
 
@@ -483,6 +561,10 @@ mod macros {
 use crate::builtins::builtins;
 use crate::context::{predefined_from, Context};
 use crate::macros::transpile;
+use crate::transpile_item::{
+    escaped_name, generic_args, generic_params, transpile_body, transpile_fn,
+    transpile_union_decls, where_clause, BodyResult,
+};
 
 use crate::sanitize::sanitize_name;
 use macros::punct;
@@ -492,6 +574,7 @@ punct!(
     TypeElement,
     TupleTypeMember,
     Param,
+    FunctionTypeParam,
     FunctionCallArg,
     ConstructorCallArg,
     ClosureArgument,
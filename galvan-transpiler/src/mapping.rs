@@ -5,10 +5,12 @@ use std::collections::HashMap;
 #[derive(Debug, Default)]
 pub(crate) struct Mapping {
     pub(crate) types: HashMap<TypeIdent, RustType>,
+    /// Rust paths of traits that can be used as bounds for type parameters
+    pub(crate) traits: HashMap<TypeIdent, Box<str>>,
 }
 
 impl Mapping {
-    pub(crate) fn get_owned(&self, type_id: &TypeIdent) -> Cow<str> {
+    pub(crate) fn get_owned(&self, type_id: &TypeIdent) -> Cow<'_, str> {
         self.types
             .get(type_id)
             .map(RustType::owned)
@@ -16,7 +18,7 @@ impl Mapping {
             .unwrap_or_else(|| type_id.to_string().into())
     }
 
-    pub(crate) fn get_borrowed(&self, type_id: &TypeIdent) -> Cow<str> {
+    pub(crate) fn get_borrowed(&self, type_id: &TypeIdent) -> Cow<'_, str> {
         self.types
             .get(type_id)
             .map(RustType::borrowed)
@@ -24,7 +26,7 @@ impl Mapping {
             .unwrap_or_else(|| type_id.to_string().into())
     }
 
-    pub(crate) fn get_mut_borrowed(&self, type_id: &TypeIdent) -> Cow<str> {
+    pub(crate) fn get_mut_borrowed(&self, type_id: &TypeIdent) -> Cow<'_, str> {
         self.types
             .get(type_id)
             .map(RustType::mut_borrowed)
@@ -32,6 +34,13 @@ impl Mapping {
             .unwrap_or_else(|| type_id.to_string().into())
     }
 
+    pub(crate) fn get_trait(&self, trait_id: &TypeIdent) -> Cow<'_, str> {
+        self.traits
+            .get(trait_id)
+            .map(|path| Cow::Borrowed(path.as_ref()))
+            .unwrap_or_else(|| trait_id.to_string().into())
+    }
+
     pub(crate) fn is_copy(&self, type_id: &TypeIdent) -> bool {
         self.types
             .get(type_id)
@@ -45,7 +54,8 @@ macro_rules! mapping {
         {
             use crate::mapping::{Mapping, RustType};
             let types = ::std::collections::HashMap::new();
-            let mut mapping = Mapping { types };
+            let traits = ::std::collections::HashMap::new();
+            let mut mapping = Mapping { types, traits };
             $(
                 crate::mapping::mapping_insert!(mapping, $tokens);
             )*
@@ -54,6 +64,11 @@ macro_rules! mapping {
     };
 }
 macro_rules! mapping_insert {
+    ($mapping:ident, (trait $trait_id:literal => $path:literal)) => {
+        $mapping
+            .traits
+            .insert(::galvan_ast::TypeIdent::new($trait_id), $path.into());
+    };
     ($mapping:ident, ($type_id:literal => $owned:literal, $borrowed:literal, $mut_borrowed:literal, copy)) => {
         $mapping.types.insert(
             ::galvan_ast::TypeIdent::new($type_id),
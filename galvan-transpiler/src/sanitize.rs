@@ -1,7 +1,10 @@
 use std::borrow::Cow;
 
-pub(crate) fn sanitize_name(name: &str) -> Cow<str> {
-    if RUST_KEYWORDS.contains(&name) {
+pub(crate) fn sanitize_name(name: &str) -> Cow<'_, str> {
+    if let Some(index) = name.strip_prefix('#') {
+        // Numbered closure parameters e.g.: `#0`
+        format!("__{}", index).into()
+    } else if RUST_KEYWORDS.contains(&name) {
         format!("r#{}", name).into()
     } else {
         name.into()
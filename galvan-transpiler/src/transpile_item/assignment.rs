@@ -1,10 +1,17 @@
 use crate::context::Context;
 use crate::macros::{impl_transpile_variants, transpile};
+use crate::transpile_item::postfix::transpile_key;
+use crate::transpile_item::r#type::{is_union_value, transpile_typed_value, transpile_union_value};
+use crate::transpile_item::statement::transpile_owned_expression;
+use crate::type_inference::InferType;
 use crate::Transpile;
-use galvan_ast::{Assignment, AssignmentOperator, AssignmentTarget, Ownership, TopExpression};
+use galvan_ast::{
+    AccessExpression, Assignment, AssignmentOperator, AssignmentTarget, Ownership, TopExpression,
+    TypeElement,
+};
 use galvan_resolver::Scope;
 
-impl_transpile_variants!(AssignmentTarget; Ident, MemberChain);
+impl_transpile_variants!(AssignmentTarget; Ident, MemberChain, AccessExpression);
 
 impl Transpile for Assignment {
     fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
@@ -26,11 +33,36 @@ impl Transpile for Assignment {
                 Ownership::Ref => todo!("Handle assignment to ref variable"),
             }),
             AssignmentTarget::MemberChain(_) => "",
+            AssignmentTarget::AccessExpression(access) => match access.base.infer_type(scope) {
+                Some(TypeElement::Dictionary(dict)) => {
+                    return transpile_dict_assignment(
+                        ctx,
+                        scope,
+                        access,
+                        &dict.value,
+                        operator,
+                        exp,
+                    );
+                }
+                Some(TypeElement::OrderedDictionary(dict)) => {
+                    return transpile_dict_assignment(
+                        ctx,
+                        scope,
+                        access,
+                        &dict.value,
+                        operator,
+                        exp,
+                    );
+                }
+                _ => "",
+            },
         };
 
         match operator {
             AssignmentOperator::Assign => {
-                transpile!(ctx, scope, "{prefix}{} = {}", target, exp)
+                let ty = target_type(scope, target);
+                let value = transpile_assigned_value(ctx, scope, ty.as_ref(), exp);
+                transpile!(ctx, scope, "{prefix}{} = {value}", target)
             }
             AssignmentOperator::AddAssign => {
                 transpile!(ctx, scope, "{prefix}{} += {}", target, exp)
@@ -55,3 +87,65 @@ impl Transpile for Assignment {
 }
 
 impl_transpile_variants!(TopExpression; Expression, ElseExpression);
+
+/// The declared type of the assigned place, if it is known
+fn target_type(scope: &Scope, target: &AssignmentTarget) -> Option<TypeElement> {
+    match target {
+        AssignmentTarget::Ident(ident) => scope.get_variable(ident)?.ty.clone(),
+        AssignmentTarget::MemberChain(chain) => chain.infer_type(scope),
+        AssignmentTarget::AccessExpression(access) => match access.base.infer_type(scope)? {
+            TypeElement::Array(array) => Some(array.elements),
+            _ => None,
+        },
+    }
+}
+
+/// Values assigned to a union are wrapped into its enum, also as elements of collection literals
+fn transpile_assigned_value(
+    ctx: &Context,
+    scope: &mut Scope,
+    ty: Option<&TypeElement>,
+    exp: &TopExpression,
+) -> String {
+    match (ty, exp) {
+        (Some(ty), TopExpression::Expression(expr)) if is_union_value(ty, expr) => {
+            transpile_typed_value(ctx, scope, ty, expr)
+        }
+        (Some(TypeElement::Union(union)), exp) => {
+            let value = exp.transpile(ctx, scope);
+            transpile_union_value(ctx, union, &value)
+        }
+        (_, exp) => exp.transpile(ctx, scope),
+    }
+}
+
+/// Assigning to a key inserts it into the dictionary, other assignment operators require the key to be present
+fn transpile_dict_assignment(
+    ctx: &Context,
+    scope: &mut Scope,
+    access: &AccessExpression,
+    value_type: &TypeElement,
+    operator: &AssignmentOperator,
+    exp: &TopExpression,
+) -> String {
+    let base = access.base.transpile(ctx, scope);
+    if *operator == AssignmentOperator::Assign {
+        let key = transpile_owned_expression(ctx, &access.index, scope);
+        let value = transpile_assigned_value(ctx, scope, Some(value_type), exp);
+        return format!("_ = {base}.insert({key}, {value})");
+    }
+
+    let key = transpile_key(ctx, scope, &access.index);
+    let target = format!("{base}.get_mut({key}).expect(\"Key is not present in the dictionary\")");
+    match operator {
+        AssignmentOperator::AddAssign => transpile!(ctx, scope, "*{target} += {}", exp),
+        AssignmentOperator::SubAssign => transpile!(ctx, scope, "*{target} -= {}", exp),
+        AssignmentOperator::MulAssign => transpile!(ctx, scope, "*{target} *= {}", exp),
+        AssignmentOperator::DivAssign => transpile!(ctx, scope, "*{target} /= {}", exp),
+        AssignmentOperator::RemAssign => transpile!(ctx, scope, "*{target} %= {}", exp),
+        AssignmentOperator::PowAssign => {
+            transpile!(ctx, scope, "*{target} = {target}.pow({})", exp)
+        }
+        AssignmentOperator::Assign => unreachable!(),
+    }
+}
@@ -1,10 +1,16 @@
 use crate::context::Context;
 use crate::macros::{impl_transpile, transpile};
+use crate::transpile_item::fn_decl::declare_param;
+use crate::transpile_item::function_call::{if_parts, try_parts};
+use crate::transpile_item::r#type::transpile_union_value;
+use crate::transpile_item::statement::{transpile_body, BodyResult};
+use crate::type_inference::InferType;
 use crate::Transpile;
 use galvan_ast::{
-    Block, Closure, ClosureArgument, DeclModifier, ElseExpression, LetKeyword, Ownership, Param,
+    Block, Closure, ClosureArgument, DeclModifier, ElseExpression, Expression, FunctionTypeItem,
+    Ident, LetKeyword, Ownership, Param, SingleExpression, TypeElement,
 };
-use galvan_resolver::{Scope, Variable};
+use galvan_resolver::{FunctionContext, Scope, Variable};
 use itertools::Itertools;
 
 impl Transpile for Closure {
@@ -19,11 +25,23 @@ pub(crate) fn transpile_closure(
     closure: &Closure,
     deref_args: bool,
 ) -> String {
-    let mut closure_scope = Scope::child(scope);
+    // `return` exits the closure and `break` cannot exit loops outside of the closure
+    let context = FunctionContext {
+        return_type: None,
+        is_async: false,
+        is_const: false,
+    };
+    let mut closure_scope = Scope::function_body(scope, context);
     let scope = &mut closure_scope;
 
-    let arguments = closure
-        .arguments
+    let implicit_arguments;
+    let arguments = if closure.arguments.is_empty() {
+        implicit_arguments = ImplicitParameters::of(ctx, scope, &closure.block, None).arguments();
+        &implicit_arguments
+    } else {
+        &closure.arguments
+    };
+    let arguments = arguments
         .iter()
         .map(|a| transpile_closure_argument(ctx, scope, a, deref_args))
         .join(", ");
@@ -31,9 +49,257 @@ pub(crate) fn transpile_closure(
     transpile!(ctx, scope, "|{}| {}", arguments, block)
 }
 
+/// Transpiles a closure that is passed as a value of the given function type
+///
+/// The closure arguments take the parameter types of the function type, `name` is used for errors
+pub(crate) fn transpile_typed_closure(
+    ctx: &Context,
+    scope: &mut Scope,
+    closure: &Closure,
+    function: &FunctionTypeItem,
+    name: &Ident,
+) -> String {
+    let arity = function.parameters.len();
+    // Parameters that are not used by a closure without parameter list are discarded
+    let arguments = if closure.arguments.is_empty() {
+        ImplicitParameters::of(ctx, scope, &closure.block, Some(arity)).typed_arguments(arity, name)
+    } else if closure.arguments.len() == arity {
+        closure.arguments.iter().cloned().map(Some).collect()
+    } else {
+        todo!(
+            "TRANSPILER ERROR: `{name}` expects a closure with {arity} arguments, but the closure has {}",
+            closure.arguments.len()
+        )
+    };
+
+    let context = FunctionContext {
+        return_type: function.return_type.clone(),
+        is_async: false,
+        is_const: false,
+    };
+    let mut closure_scope = Scope::function_body(scope, context);
+    let scope = &mut closure_scope;
+
+    let arguments = arguments
+        .iter()
+        .zip(&function.parameters)
+        .map(|(arg, param)| {
+            let Some(arg) = arg else {
+                return "_".to_owned();
+            };
+            if arg.ty.as_ref().is_some_and(|ty| *ty != param.param_type) {
+                todo!(
+                    "TRANSPILER ERROR: Argument `{}` of the closure passed as `{name}` must have the type {:?}",
+                    arg.ident,
+                    param.param_type
+                )
+            }
+            let param = Param {
+                decl_modifier: param.decl_modifier,
+                label: None,
+                identifier: arg.ident.clone(),
+                param_type: param.param_type.clone(),
+                default_value: None,
+            };
+            declare_param(ctx, scope, &param);
+            arg.ident.transpile(ctx, scope)
+        })
+        .join(", ");
+
+    let result = match &function.return_type {
+        Some(expected) => {
+            if let (TypeElement::Plain(expected), Some(TypeElement::Plain(actual))) =
+                (expected, closure.block.infer_type(scope))
+            {
+                // Number literals are converted to the expected numeric type by Rust
+                if expected.ident != actual.ident && !actual.ident.as_str().starts_with("__") {
+                    todo!(
+                        "TRANSPILER ERROR: The closure passed as `{name}` must return {}, but returns {}",
+                        expected.ident,
+                        actual.ident
+                    )
+                }
+            }
+            BodyResult::Owned
+        }
+        None => BodyResult::Discarded,
+    };
+    let block = transpile_body(ctx, scope, &closure.block.body, result);
+    format!("|{arguments}| {block}")
+}
+
+/// Transpiles a value that is stored with a function type, e.g. in a field, which requires boxing it
+pub(crate) fn transpile_function_value(
+    ctx: &Context,
+    scope: &mut Scope,
+    value: &Expression,
+    function: &FunctionTypeItem,
+    name: &Ident,
+) -> String {
+    let value = match value {
+        Expression::Closure(closure) => {
+            transpile_typed_closure(ctx, scope, closure, function, name)
+        }
+        value => value.transpile(ctx, scope),
+    };
+    format!("::std::boxed::Box::new({value})")
+}
+
 impl_transpile!(Block, "{}", body);
-// TODO: Allow a second variant that takes an error as an argument
-impl_transpile!(ElseExpression, "({}).__or_else(|| {})", receiver, block);
+
+impl Transpile for ElseExpression {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        // Branches of different types are converted into the union of both types
+        let union = match self.infer_type(scope) {
+            Some(TypeElement::Union(union)) => Some(union),
+            _ => None,
+        };
+        // The value of the receiver is optional, if the else branch is an `if` without an else branch
+        let is_open = self.is_open();
+
+        let wrap = |value: &str| match &union {
+            _ if is_open => format!("Some({value})"),
+            Some(union) => transpile_union_value(ctx, union, value),
+            None => value.to_owned(),
+        };
+        let body = |scope: &mut Scope, block: &Block| {
+            let block = block.transpile(ctx, scope);
+            if is_open || union.is_some() {
+                format!("{{ {} }}", wrap(&block))
+            } else {
+                block
+            }
+        };
+        let else_body = |scope: &mut Scope| {
+            let block = self.block.transpile(ctx, scope);
+            match &union {
+                Some(union) => format!("{{ {} }}", transpile_union_value(ctx, union, &block)),
+                None => block,
+            }
+        };
+
+        // Both branches are transpiled inline, so `return`, `throw` and `break` work inside of them
+        if let SingleExpression::FunctionCall(call) = self.receiver.as_ref() {
+            match call.identifier.as_str() {
+                "if" => {
+                    if self.error.is_some() {
+                        todo!("TRANSPILER ERROR: Only the else branch of a try can bind an error")
+                    }
+                    let (condition, block) = if_parts(call);
+                    let condition = condition.transpile(ctx, scope);
+                    let body = body(scope, block);
+                    let else_body = else_body(scope);
+                    return format!("if {condition} {body} else {else_body}");
+                }
+                "try" => {
+                    let (fallible, closure) = try_parts(call);
+                    let fallible_type = fallible.infer_type(scope);
+                    let receiver = fallible.transpile(ctx, scope);
+
+                    let mut body_scope = Scope::child(scope);
+                    let value = declare_try_value(ctx, &mut body_scope, closure, &fallible_type);
+                    let body = body(&mut body_scope, &closure.block);
+
+                    let mut else_scope = Scope::child(scope);
+                    return match &self.error {
+                        Some(error) => {
+                            let error =
+                                declare_try_error(ctx, &mut else_scope, error, fallible_type);
+                            let else_body = else_body(&mut else_scope);
+                            format!("match ({receiver}).__borrow_result() {{ Ok({value}) => {body}, Err({error}) => {else_body} }}")
+                        }
+                        None => {
+                            let else_body = else_body(&mut else_scope);
+                            format!("match ({receiver}).__borrow_inner() {{ Some({value}) => {body}, None => {else_body} }}")
+                        }
+                    };
+                }
+                _ => {}
+            }
+        }
+
+        let receiver_type = self.receiver.infer_type(scope);
+        let receiver = self.receiver.transpile(ctx, scope);
+        let value = wrap("value");
+        let mut else_scope = Scope::child(scope);
+        match &self.error {
+            Some(error) => {
+                let error = declare_try_error(ctx, &mut else_scope, error, receiver_type);
+                let else_body = else_body(&mut else_scope);
+                format!("match ({receiver}).__to_result() {{ Ok(value) => {value}, Err({error}) => {else_body} }}")
+            }
+            None => {
+                let else_body = else_body(&mut else_scope);
+                format!("match ({receiver}).__to_option() {{ Some(value) => {value}, None => {else_body} }}")
+            }
+        }
+    }
+}
+
+/// Declares the unwrapped value of a `try` and returns the pattern that binds it
+///
+/// The value is available as `it`, unless it is named using closure parameter syntax
+pub(crate) fn declare_try_value(
+    ctx: &Context,
+    scope: &mut Scope,
+    closure: &Closure,
+    fallible_type: &Option<TypeElement>,
+) -> String {
+    let inner_type = match fallible_type {
+        Some(TypeElement::Optional(optional)) => Some(optional.some.clone()),
+        Some(TypeElement::Result(result)) => Some(result.success.clone()),
+        _ => None,
+    };
+    let (ident, ty) = match closure.arguments.as_slice() {
+        [] => (Ident::new("it"), inner_type),
+        [arg] => (arg.ident.clone(), arg.ty.clone().or(inner_type)),
+        _ => todo!("TRANSPILER ERROR: try binds exactly one value"),
+    };
+
+    // Copyable values are copied out of the optional or result
+    let is_copy =
+        matches!(&ty, Some(TypeElement::Plain(plain)) if ctx.mapping.is_copy(&plain.ident));
+    scope.declare_variable(Variable {
+        ident: ident.clone(),
+        modifier: DeclModifier::Let(LetKeyword),
+        ty,
+        ownership: if is_copy {
+            Ownership::Copy
+        } else {
+            Ownership::Borrowed
+        },
+    });
+
+    let ident = ident.transpile(ctx, scope);
+    if is_copy {
+        format!("&{ident}")
+    } else {
+        ident
+    }
+}
+
+/// Declares the error that is bound by an else branch and returns its transpiled name
+fn declare_try_error(
+    ctx: &Context,
+    scope: &mut Scope,
+    error: &ClosureArgument,
+    fallible_type: Option<TypeElement>,
+) -> String {
+    let error_type = match fallible_type {
+        Some(TypeElement::Optional(_)) => {
+            todo!("TRANSPILER ERROR: Optionals do not carry an error, only results can bind an error in their else branch")
+        }
+        Some(TypeElement::Result(result)) => result.error,
+        _ => None,
+    };
+    scope.declare_variable(Variable {
+        ident: error.ident.clone(),
+        modifier: DeclModifier::Let(LetKeyword),
+        ty: error.ty.clone().or(error_type),
+        ownership: Ownership::Borrowed,
+    });
+    error.ident.transpile(ctx, scope)
+}
 
 impl Transpile for ClosureArgument {
     fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
@@ -58,9 +324,11 @@ fn transpile_closure_argument(
     let prefix = if deref { "&" } else { "" };
     if let Some(ty) = &arg.ty {
         let param = Param {
-            identifier: arg.ident.clone(),
             decl_modifier: None,
+            label: None,
+            identifier: arg.ident.clone(),
             param_type: ty.clone(),
+            default_value: None,
         };
         transpile!(ctx, scope, "{prefix}{}", param)
     } else {
@@ -68,3 +336,84 @@ fn transpile_closure_argument(
         transpile!(ctx, scope, "{prefix}{}", arg.ident)
     }
 }
+
+/// The implicit parameters `it` or `#0`, `#1`, ... that are used by a closure without parameter list
+#[derive(Default)]
+struct ImplicitParameters {
+    it: bool,
+    /// Number of numbered parameters, which is one more than the highest used index
+    numbered: usize,
+}
+
+impl ImplicitParameters {
+    /// Transpiles the body once in a scope that records the uses of implicit parameters
+    ///
+    /// If the arity is known, names that the closure cannot bind refer to variables of the enclosing scope,
+    /// e.g. `it` of an unnamed `for` loop inside of a closure without parameters
+    fn of(ctx: &Context, scope: &Scope, block: &Block, arity: Option<usize>) -> Self {
+        let context = FunctionContext {
+            return_type: None,
+            is_async: false,
+            is_const: false,
+        };
+        let mut body_scope = Scope::implicit_closure_body(scope, context);
+        block.transpile(ctx, &mut body_scope);
+        let used = body_scope.implicit_parameters.take().unwrap_or_default();
+
+        let mut parameters = Self::default();
+        for ident in used.into_inner() {
+            let name = ident.as_str();
+            let index = name.strip_prefix('#').map(|index| {
+                index
+                    .parse::<usize>()
+                    .unwrap_or_else(|_| todo!("TRANSPILER ERROR: Invalid closure parameter {name}"))
+            });
+            let bound = arity.is_none_or(|arity| match index {
+                Some(index) => index < arity,
+                None => arity == 1,
+            });
+            if !bound && scope.get_variable(&ident).is_some() {
+                continue;
+            }
+            match index {
+                Some(index) => parameters.numbered = parameters.numbered.max(index + 1),
+                None => parameters.it = true,
+            }
+        }
+        parameters
+    }
+
+    /// Arguments for a closure of the given function type, unused parameters are `None`
+    fn typed_arguments(&self, arity: usize, name: &Ident) -> Vec<Option<ClosureArgument>> {
+        if self.it && arity != 1 {
+            todo!("TRANSPILER ERROR: `{name}` expects a closure with {arity} arguments, `it` can only be used with one")
+        }
+        if self.numbered > arity {
+            todo!(
+                "TRANSPILER ERROR: `{name}` expects a closure with {arity} arguments, but the closure uses #{}",
+                self.numbered - 1
+            )
+        }
+        let mut arguments = self.arguments().into_iter().map(Some).collect_vec();
+        arguments.resize(arity, None);
+        arguments
+    }
+
+    /// Arguments of the implicit parameters, in the order in which they are passed
+    fn arguments(&self) -> Vec<ClosureArgument> {
+        if self.it && self.numbered > 0 {
+            todo!("TRANSPILER ERROR: A closure cannot use both `it` and numbered parameters")
+        }
+        let names = if self.it {
+            vec![Ident::new("it")]
+        } else {
+            (0..self.numbered)
+                .map(|index| Ident::new(format!("#{index}")))
+                .collect()
+        };
+        names
+            .into_iter()
+            .map(|ident| ClosureArgument { ident, ty: None })
+            .collect()
+    }
+}
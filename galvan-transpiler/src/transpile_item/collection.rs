@@ -42,8 +42,8 @@ impl_transpile!(
     elements
 );
 
-impl Transpile for OrderedDictLiteral {
-    fn transpile(&self, _ctx: &Context, _scope: &mut Scope) -> String {
-        todo!("Transpile OrderedDictLiteral")
-    }
-}
+impl_transpile!(
+    OrderedDictLiteral,
+    "::galvan::std::IndexMap::from([{}])",
+    elements
+);
@@ -0,0 +1,124 @@
+use crate::context::Context;
+use crate::macros::transpile;
+use crate::transpile_item::statement::match_ident;
+use crate::Transpile;
+use galvan_ast::{
+    ComparisonOperator, ConstDecl, Expression, InfixOperator, Literal, OperatorTree,
+    OperatorTreeNode, PrefixExpression, PrefixOperator, SimpleExpression, SingleExpression,
+    TopExpression,
+};
+use galvan_resolver::{Lookup, Scope};
+
+impl Transpile for ConstDecl {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        if !is_const_value(ctx, &self.value) {
+            todo!(
+                "TRANSPILER ERROR: The value of the constant `{}` cannot be evaluated at compile time",
+                self.identifier
+            )
+        }
+
+        transpile!(
+            ctx,
+            scope,
+            "{} const {}: {} = {};",
+            self.visibility,
+            self.identifier,
+            self.ty,
+            self.value
+        )
+    }
+}
+
+/// Checks if an expression can be evaluated at compile time
+///
+/// Constants are built from literals, other constants, builtin operators and calls to const functions
+fn is_const_value(ctx: &Context, value: &Expression) -> bool {
+    match value {
+        Expression::OperatorTree(tree) => is_const_operation(ctx, tree),
+        Expression::PrefixExpression(prefix) => is_const_prefix(ctx, prefix),
+        Expression::SingleExpression(expression) => is_const_single(ctx, expression),
+        _ => false,
+    }
+}
+
+fn is_const_operation(ctx: &Context, tree: &OperatorTree) -> bool {
+    let operator = match &tree.operator {
+        InfixOperator::Arithmetic(_) | InfixOperator::Bitwise(_) | InfixOperator::Logical(_) => {
+            true
+        }
+        InfixOperator::Comparison(op) => !matches!(
+            op,
+            ComparisonOperator::Identical | ComparisonOperator::NotIdentical
+        ),
+        InfixOperator::CustomInfix(op) => ctx
+            .lookup
+            .resolve_infix_operator(op.as_str())
+            .is_some_and(|func| func.signature.constness.is_const()),
+        InfixOperator::Collection(_) | InfixOperator::NullCoalescing(_) => false,
+    };
+    operator && is_const_operand(ctx, &tree.left) && is_const_operand(ctx, &tree.right)
+}
+
+fn is_const_operand(ctx: &Context, operand: &OperatorTreeNode) -> bool {
+    match operand {
+        OperatorTreeNode::Operation(tree) => is_const_operation(ctx, tree),
+        OperatorTreeNode::SimpleExpression(SimpleExpression::PrefixExpression(prefix)) => {
+            is_const_prefix(ctx, prefix)
+        }
+        OperatorTreeNode::SimpleExpression(SimpleExpression::SingleExpression(expression)) => {
+            is_const_single(ctx, expression)
+        }
+        OperatorTreeNode::SimpleExpression(SimpleExpression::MemberChain(_)) => false,
+    }
+}
+
+fn is_const_prefix(ctx: &Context, prefix: &PrefixExpression) -> bool {
+    let operator = match &prefix.operator {
+        PrefixOperator::Not | PrefixOperator::Negate | PrefixOperator::BitwiseNot => true,
+        PrefixOperator::CustomPrefix(op) => ctx
+            .lookup
+            .resolve_prefix_operator(op.as_str())
+            .is_some_and(|func| func.signature.constness.is_const()),
+    };
+    operator
+        && match prefix.operand.as_ref() {
+            SimpleExpression::PrefixExpression(prefix) => is_const_prefix(ctx, prefix),
+            SimpleExpression::SingleExpression(expression) => is_const_single(ctx, expression),
+            SimpleExpression::MemberChain(_) => false,
+        }
+}
+
+fn is_const_single(ctx: &Context, expression: &SingleExpression) -> bool {
+    // Variables passed to constructors are copied with `to_owned`, which cannot be called in constants
+    let is_const_field =
+        |value: &Expression| !matches!(value, match_ident!(_)) && is_const_value(ctx, value);
+    match expression {
+        // Strings are created with `format!`
+        SingleExpression::Literal(Literal::StringLiteral(_)) => false,
+        SingleExpression::Literal(_) => true,
+        SingleExpression::Ident(ident) => ctx.lookup.resolve_const(ident).is_some(),
+        SingleExpression::Group(group) => match group.0.as_ref() {
+            TopExpression::Expression(expression) => is_const_value(ctx, expression),
+            TopExpression::ElseExpression(_) => false,
+        },
+        SingleExpression::FunctionCall(call) => {
+            ctx.lookup
+                .resolve_function(None, &call.identifier, &call.labels())
+                .is_some_and(|func| func.signature.constness.is_const())
+                && call
+                    .arguments
+                    .iter()
+                    .all(|arg| arg.modifier.is_none() && is_const_value(ctx, &arg.expression))
+        }
+        SingleExpression::ConstructorCall(constructor) => constructor
+            .arguments
+            .iter()
+            .all(|arg| is_const_field(&arg.expression)),
+        SingleExpression::EnumConstructor(constructor) => constructor
+            .arguments
+            .iter()
+            .all(|arg| is_const_field(&arg.expression)),
+        _ => false,
+    }
+}
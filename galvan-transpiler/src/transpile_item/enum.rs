@@ -0,0 +1,198 @@
+use crate::context::Context;
+use crate::macros::transpile;
+use crate::transpile_item::r#struct::DERIVE;
+use crate::transpile_item::r#type::{check_where_clause, generic_params, where_clause};
+use crate::transpile_item::statement::transpile_owned_expression;
+use crate::Transpile;
+use galvan_ast::{
+    DeclModifier, EnumConstructor, EnumConstructorArg, EnumTypeDecl, EnumTypeVariant,
+    EnumVariantFields, StructTypeMember, TypeDecl, TypeIdent,
+};
+use galvan_resolver::{Lookup, Scope};
+use itertools::Itertools;
+
+/// Name of the Rust enum that holds the variants of an enum with shared fields
+pub(crate) fn variant_enum_name(ident: &TypeIdent) -> String {
+    format!("__{ident}Variant")
+}
+
+/// Name of the field that holds the variant of an enum with shared fields
+pub(crate) static VARIANT_FIELD: &str = "__variant";
+
+pub(crate) fn resolve_enum<'a>(ctx: &'a Context, ident: &TypeIdent) -> &'a EnumTypeDecl {
+    let Some(decl) = ctx.lookup.resolve_type(ident) else {
+        todo!("TRANSPILER ERROR: Type {ident} not found")
+    };
+    let TypeDecl::Enum(decl) = &decl.item else {
+        todo!("TRANSPILER ERROR: {ident} is not an enum type")
+    };
+    decl
+}
+
+impl Transpile for EnumTypeDecl {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        check_where_clause(self.where_clause.as_ref(), &self.generics(), &self.ident);
+        let visibility = self.visibility.transpile(ctx, scope);
+        let variants = self
+            .variants
+            .iter()
+            .map(|v| v.transpile(ctx, scope))
+            .join(",\n");
+
+        if !self.has_shared_fields() {
+            let where_clause = where_clause(ctx, &self.generics(), &self.where_clause);
+            let generics = generic_params(&self.generics());
+            return transpile!(
+                ctx,
+                scope,
+                "{DERIVE} {visibility} enum {}{generics}{where_clause} {{\n{variants}\n}}",
+                self.ident
+            );
+        }
+
+        // Enums with shared fields are transpiled to a struct that contains the shared fields and the actual enum
+        let bounds = where_clause(ctx, &self.generics(), &self.where_clause);
+        let variant_bounds = where_clause(ctx, &self.variant_generics(), &self.where_clause);
+        let generics = generic_params(&self.generics());
+        let variant_generics = generic_params(&self.variant_generics());
+        let variant_enum = variant_enum_name(&self.ident);
+        transpile!(
+            ctx,
+            scope,
+            "{DERIVE} {visibility} struct {}{generics}{bounds} {{\n{},\npub(crate) {VARIANT_FIELD}: {variant_enum}{variant_generics}\n}}\n\n{DERIVE} {visibility} enum {variant_enum}{variant_generics}{variant_bounds} {{\n{variants}\n}}",
+            self.ident,
+            self.fields
+        )
+    }
+}
+
+impl Transpile for EnumTypeVariant {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        // Variant names are not resolved as types
+        let ident = self.ident.as_str();
+        match &self.fields {
+            EnumVariantFields::Unit => ident.to_owned(),
+            EnumVariantFields::Tuple(fields) => {
+                transpile!(ctx, scope, "{ident}({})", fields)
+            }
+            EnumVariantFields::Struct(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|f| transpile_variant_field(ctx, scope, f))
+                    .join(", ");
+                format!("{ident} {{ {fields} }}")
+            }
+        }
+    }
+}
+
+fn transpile_variant_field(ctx: &Context, scope: &mut Scope, field: &StructTypeMember) -> String {
+    match field.decl_modifier {
+        Some(DeclModifier::Ref(_)) => transpile!(
+            ctx,
+            scope,
+            "{}: std::sync::Arc<std::sync::Mutex<{}>>",
+            field.ident,
+            field.r#type
+        ),
+        Some(_) => todo!("TRANSPILER ERROR: Only ref is allowed as modifier for enum fields"),
+        None => transpile!(ctx, scope, "{}: {}", field.ident, field.r#type),
+    }
+}
+
+impl Transpile for EnumConstructor {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        let decl = resolve_enum(ctx, &self.enum_ident);
+        let Some(variant) = decl.variant(&self.variant) else {
+            todo!(
+                "TRANSPILER ERROR: Enum {} has no variant {}",
+                self.enum_ident,
+                self.variant
+            )
+        };
+
+        let (shared, args): (Vec<_>, Vec<_>) = self.arguments.iter().partition(|arg| {
+            arg.ident
+                .as_ref()
+                .is_some_and(|ident| decl.fields.iter().any(|f| f.ident == *ident))
+        });
+        if shared.len() != decl.fields.len() {
+            todo!(
+                "TRANSPILER ERROR: All shared fields of {} must be given when creating a variant",
+                self.enum_ident
+            )
+        }
+
+        let enum_name = if decl.has_shared_fields() {
+            variant_enum_name(&self.enum_ident)
+        } else {
+            self.enum_ident.transpile(ctx, scope)
+        };
+        let variant = transpile_variant_value(ctx, scope, &enum_name, variant, &args);
+
+        if decl.has_shared_fields() {
+            let shared = shared
+                .iter()
+                .map(|arg| transpile_named_arg(ctx, scope, arg))
+                .join(", ");
+            transpile!(
+                ctx,
+                scope,
+                "{} {{ {shared}, {VARIANT_FIELD}: {variant} }}",
+                self.enum_ident
+            )
+        } else {
+            variant
+        }
+    }
+}
+
+fn transpile_variant_value(
+    ctx: &Context,
+    scope: &mut Scope,
+    enum_name: &str,
+    variant: &EnumTypeVariant,
+    args: &[&EnumConstructorArg],
+) -> String {
+    let ident = &variant.ident;
+    match &variant.fields {
+        EnumVariantFields::Unit => {
+            if !args.is_empty() {
+                todo!("TRANSPILER ERROR: Variant {ident} does not have any fields")
+            }
+            format!("{enum_name}::{ident}")
+        }
+        EnumVariantFields::Tuple(fields) => {
+            if args.len() != fields.len() || args.iter().any(|arg| arg.ident.is_some()) {
+                todo!(
+                    "TRANSPILER ERROR: Variant {ident} expects {} unnamed arguments",
+                    fields.len()
+                )
+            }
+            let args = args
+                .iter()
+                .map(|arg| transpile_owned_expression(ctx, &arg.expression, scope))
+                .join(", ");
+            format!("{enum_name}::{ident}({args})")
+        }
+        EnumVariantFields::Struct(fields) => {
+            if args.len() != fields.len() || args.iter().any(|arg| arg.ident.is_none()) {
+                todo!(
+                    "TRANSPILER ERROR: Variant {ident} expects {} named arguments",
+                    fields.len()
+                )
+            }
+            let args = args
+                .iter()
+                .map(|arg| transpile_named_arg(ctx, scope, arg))
+                .join(", ");
+            format!("{enum_name}::{ident} {{ {args} }}")
+        }
+    }
+}
+
+fn transpile_named_arg(ctx: &Context, scope: &mut Scope, arg: &EnumConstructorArg) -> String {
+    let ident = arg.ident.as_ref().expect("Argument should be named");
+    let expression = transpile_owned_expression(ctx, &arg.expression, scope);
+    transpile!(ctx, scope, "{}: {expression}", ident)
+}
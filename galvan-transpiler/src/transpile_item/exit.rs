@@ -0,0 +1,64 @@
+use crate::context::Context;
+use crate::transpile_item::r#type::{is_union_value, transpile_typed_value, transpile_union_value};
+use crate::transpile_item::statement::transpile_owned_expression;
+use crate::type_inference::InferType;
+use crate::Transpile;
+use galvan_ast::{Expression, Return, Throw, TypeElement};
+use galvan_resolver::Scope;
+
+impl Transpile for Return {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        match &self.value {
+            Some(value) => format!("return {}", transpile_return_value(ctx, scope, value)),
+            None => "return".into(),
+        }
+    }
+}
+
+/// Transpiles a value that is returned from the surrounding function
+///
+/// The value is wrapped in `Ok` if the function returns a result and the value is not a result already
+pub(crate) fn transpile_return_value(
+    ctx: &Context,
+    scope: &mut Scope,
+    value: &Expression,
+) -> String {
+    let return_type = scope
+        .get_function()
+        .and_then(|function| function.return_type.clone());
+    match return_type {
+        Some(TypeElement::Result(_)) => {
+            let value_type = value.infer_type(scope);
+            let transpiled = transpile_owned_expression(ctx, value, scope);
+            match value_type {
+                Some(TypeElement::Result(_)) | None => transpiled,
+                Some(_) => format!("Ok({transpiled})"),
+            }
+        }
+        Some(ty @ TypeElement::Union(_)) if value.infer_type(scope).as_ref() == Some(&ty) => {
+            transpile_owned_expression(ctx, value, scope)
+        }
+        Some(ty) if is_union_value(&ty, value) => transpile_typed_value(ctx, scope, &ty, value),
+        _ => transpile_owned_expression(ctx, value, scope),
+    }
+}
+
+impl Transpile for Throw {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        let Some(TypeElement::Result(result)) = scope
+            .get_function()
+            .and_then(|function| function.return_type.clone())
+        else {
+            todo!("TRANSPILER ERROR: throw is only allowed in functions that return a result")
+        };
+
+        let value = transpile_owned_expression(ctx, &self.value, scope);
+        match result.error {
+            None => format!("return Err(::galvan::std::FlexError::msg({value}))"),
+            Some(TypeElement::Union(union)) => {
+                format!("return Err({})", transpile_union_value(ctx, &union, &value))
+            }
+            Some(_) => format!("return Err({value})"),
+        }
+    }
+}
@@ -1,43 +1,229 @@
 use crate::context::Context;
 use crate::macros::{impl_transpile, transpile};
 use crate::transpile_item::ident::TypeOwnership;
+use crate::transpile_item::r#type::{
+    check_where_clause, function_bound, generic_params, is_union_value, transpile_typed_value,
+    where_clause,
+};
+use crate::transpile_item::statement::{transpile_body, transpile_owned_expression, BodyResult};
+use crate::type_inference::{function_return_type, InferType};
 use crate::{FnDecl, FnSignature, Param, ParamList, Transpile};
-use galvan_ast::{DeclModifier, LetKeyword, Ownership, TypeElement};
-use galvan_resolver::{Scope, Variable};
+use galvan_ast::{
+    DeclModifier, Expression, GenericTypeItem, LetKeyword, Ownership, TypeElement, TypeIdent,
+    Visibility, WhereBound, WhereClause,
+};
+use galvan_resolver::{FunctionContext, Scope, Variable};
+use itertools::Itertools;
 
 impl Transpile for FnDecl {
     fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
-        let mut function_scope = Scope::child(scope);
-        let scope = &mut function_scope;
+        transpile_fn(ctx, scope, self).1
+    }
+}
+
+/// Transpiles a function and returns the signature it has in a trait, which includes the bounds that are required by its body
+pub(crate) fn transpile_fn(ctx: &Context, scope: &mut Scope, func: &FnDecl) -> (String, String) {
+    let return_type = return_type(scope, func);
+    let defaults = transpile_defaults(ctx, scope, &func.signature.parameters.params);
+    let context = FunctionContext {
+        return_type: return_type.clone(),
+        is_async: func.signature.asyncness.is_async(),
+        is_const: func.signature.constness.is_const(),
+    };
+    let mut function_scope = Scope::function_body(scope, context);
+    let scope = &mut function_scope;
+
+    let result = if return_type.is_some() {
+        BodyResult::Owned
+    } else {
+        BodyResult::Value
+    };
+    let signature = &func.signature;
+    let transpiled_signature = transpile_signature(
+        ctx,
+        scope,
+        signature,
+        &signature.visibility,
+        return_type.as_ref(),
+        signature.where_clause.as_ref(),
+    );
+    let block = transpile_body(ctx, scope, &func.block, result);
+    let block = if defaults.is_empty() {
+        block
+    } else {
+        format!("{{\n{defaults}\n{block}\n}}")
+    };
 
-        let signature = self.signature.transpile(ctx, scope);
-        let block = self.block.transpile(ctx, scope);
-        transpile!(ctx, scope, "{} {}", signature, block)
+    let clone_bounds = clone_bounds(signature, &block);
+    let where_clause = clone_bounds.as_ref().or(signature.where_clause.as_ref());
+    // Parameters are already declared in the function scope
+    let transpile_signature = |visibility| {
+        let scope = &mut Scope::child(scope);
+        transpile_signature(
+            ctx,
+            scope,
+            signature,
+            visibility,
+            return_type.as_ref(),
+            where_clause,
+        )
+    };
+    let transpiled_signature = match clone_bounds {
+        Some(_) => transpile_signature(&signature.visibility),
+        None => transpiled_signature,
+    };
+    let trait_signature = transpile_signature(&Visibility::Private);
+    (trait_signature, format!("{transpiled_signature} {block}"))
+}
+
+/// Replaces omitted arguments by the default value of their parameter at the start of the function body
+///
+/// Defaults are evaluated in the called function and only see the parameters that are declared before them
+fn transpile_defaults(ctx: &Context, scope: &Scope, params: &[Param]) -> String {
+    let mut scope = Scope::child(scope.root());
+    let mut defaults = vec![];
+    for param in params {
+        if let Some(default_value) = &param.default_value {
+            let ident = param.identifier.transpile(ctx, &mut scope);
+            let value = match &param.param_type {
+                ty if is_union_value(ty, default_value) => {
+                    transpile_typed_value(ctx, &mut scope, ty, default_value)
+                }
+                _ => transpile_owned_expression(ctx, default_value, &mut scope),
+            };
+            let ty = borrowed_param_type(ctx, &mut scope, param);
+            defaults.push(match &param.param_type {
+                TypeElement::Plain(plain) if ctx.mapping.is_copy(&plain.ident) => {
+                    format!("let {ident} = {ident}.unwrap_or_else(|| {value});")
+                }
+                _ => format!(
+                    "let __{ident};\nlet {ident}: {ty} = match {ident} {{ Some({ident}) => {ident}, None => {{ __{ident} = {value}; &__{ident} }} }};"
+                ),
+            });
+        }
+        declare_param(ctx, &mut scope, param);
+    }
+    defaults.join("\n")
+}
+
+/// Values are cloned when they are copied, so type parameters need to be cloneable if the body clones any value
+fn clone_bounds(signature: &FnSignature, body: &str) -> Option<WhereClause> {
+    let generics = signature.generics();
+    let clones = [".to_owned()", ".clone()", ".cloned()"]
+        .iter()
+        .any(|clone| body.contains(clone));
+    if generics.is_empty() || !clones {
+        return None;
+    }
+
+    let mut where_clause = signature.where_clause.clone().unwrap_or_default();
+    where_clause
+        .bounds
+        .extend(generics.into_iter().map(|generic| WhereBound {
+            generic: GenericTypeItem {
+                ident: generic.clone(),
+            },
+            traits: vec![TypeIdent::new("Clone")],
+        }));
+    Some(where_clause)
+}
+
+/// Return type of a function, functions defined with `=` return the type of their expression if no return type is declared
+pub(crate) fn return_type(scope: &Scope, func: &FnDecl) -> Option<TypeElement> {
+    let signature = &func.signature;
+    if !func.is_expression_body || signature.return_type.is_some() {
+        return signature.return_type.clone();
+    }
+
+    // The body only sees its parameters and the items that are visible everywhere
+    match function_return_type(scope.root(), func) {
+        Some(return_type) => Some(return_type),
+        None => todo!(
+            "TRANSPILER ERROR: The return type of `{}` cannot be inferred, declare it explicitly e.g.: `fn {}(...) -> Int = ...`",
+            signature.identifier,
+            signature.identifier
+        ),
     }
 }
 
 impl Transpile for FnSignature {
     fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
-        let visibility = self.visibility.transpile(ctx, scope);
-        let identifier = self.identifier.transpile(ctx, scope);
-        let parameters = self.parameters.transpile(ctx, scope);
-        format!(
-            "{} fn {}{}{}",
-            visibility,
-            identifier,
-            parameters,
-            self.return_type
-                .as_ref()
-                .map_or("".into(), |return_type| transpile!(
-                    ctx,
-                    scope,
-                    " -> {}",
-                    return_type
-                ))
+        transpile_signature(
+            ctx,
+            scope,
+            self,
+            &self.visibility,
+            self.return_type.as_ref(),
+            self.where_clause.as_ref(),
         )
     }
 }
 
+/// Transpiles a signature with the given visibility, return type and bounds, which may differ for the declared function
+fn transpile_signature(
+    ctx: &Context,
+    scope: &mut Scope,
+    signature: &FnSignature,
+    visibility: &Visibility,
+    return_type: Option<&TypeElement>,
+    bounds: Option<&WhereClause>,
+) -> String {
+    let visibility = visibility.transpile(ctx, scope);
+    let identifier = function_name(ctx, scope, signature);
+    // Type parameters of the receiver are declared by the surrounding impl block
+    let receiver_generics = signature
+        .receiver()
+        .map(|receiver| receiver.param_type.generics())
+        .unwrap_or_default();
+    check_where_clause(bounds, &signature.generics(), &signature.identifier);
+    let generics = signature
+        .generics()
+        .into_iter()
+        .filter(|generic| !receiver_generics.contains(generic))
+        .collect_vec();
+    // Bounds are declared on the function, so they may constrain type parameters of the receiver as well
+    let where_clause = where_clause(ctx, &signature.generics(), bounds);
+    let generics = generic_params(&generics);
+    let parameters = signature.parameters.transpile(ctx, scope);
+    let modifiers = match (
+        signature.constness.is_const(),
+        signature.asyncness.is_async(),
+    ) {
+        (true, true) => todo!(
+            "TRANSPILER ERROR: The function `{}` cannot be both const and async",
+            signature.identifier
+        ),
+        (true, false) => "const ",
+        (false, true) => "async ",
+        (false, false) => "",
+    };
+    format!(
+        "{} {}fn {}{}{}{}{}",
+        visibility,
+        modifiers,
+        identifier,
+        generics,
+        parameters,
+        return_type.map_or("".into(), |return_type| transpile!(
+            ctx,
+            scope,
+            " -> {}",
+            return_type
+        )),
+        where_clause
+    )
+}
+
+/// Name of the function in Rust, labeled functions are overloaded by their labels e.g.: `move__to`
+pub(crate) fn function_name(ctx: &Context, scope: &mut Scope, signature: &FnSignature) -> String {
+    let labels = signature.labels();
+    if labels.is_empty() {
+        signature.identifier.transpile(ctx, scope)
+    } else {
+        format!("{}__{}", signature.identifier, labels.join("__"))
+    }
+}
+
 impl_transpile!(ParamList, "({})", params);
 
 macro_rules! transpile_type {
@@ -69,42 +255,64 @@ macro_rules! transpile_type {
     }};
 }
 
+pub(crate) fn declare_param(ctx: &Context, scope: &mut Scope, param: &Param) {
+    scope.declare_variable(Variable {
+        ident: param.identifier.clone(),
+        modifier: param.decl_modifier.unwrap_or(DeclModifier::Let(LetKeyword)),
+        ty: Some(param.param_type.clone()),
+        ownership: match param.decl_modifier {
+            Some(DeclModifier::Let(_)) | None => match param.param_type {
+                TypeElement::Plain(ref plain) if ctx.mapping.is_copy(&plain.ident) => {
+                    Ownership::Copy
+                }
+                // Functions are passed as `impl Fn`, so they are borrowed when passed on
+                TypeElement::Function(_) => Ownership::Owned,
+                _ => Ownership::Borrowed,
+            },
+            Some(DeclModifier::Mut(_)) => Ownership::MutBorrowed,
+            Some(DeclModifier::Ref(_)) => Ownership::Ref,
+        },
+    });
+}
+
 impl Transpile for Param {
     fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
         let is_self = self.identifier.as_str() == "self";
+        if let Some(default_value) = &self.default_value {
+            check_default_value(scope, self, default_value);
+        }
+        declare_param(ctx, scope, self);
 
-        scope.declare_variable(Variable {
-            ident: self.identifier.clone(),
-            modifier: self.decl_modifier.unwrap_or(DeclModifier::Let(LetKeyword)),
-            ty: Some(self.param_type.clone()),
-            ownership: match self.decl_modifier {
-                Some(DeclModifier::Let(_)) | None => match self.param_type {
-                    TypeElement::Plain(ref plain) if ctx.mapping.is_copy(&plain.ident) => {
-                        Ownership::Copy
-                    }
-                    _ => Ownership::Borrowed,
-                },
-                Some(DeclModifier::Mut(_)) => Ownership::MutBorrowed,
-                Some(DeclModifier::Ref(_)) => Ownership::Ref,
-            },
-        });
-
-        match self.decl_modifier {
-            Some(DeclModifier::Let(_)) | None => {
+        match (self.decl_modifier, &self.param_type) {
+            (Some(DeclModifier::Let(_)) | None, TypeElement::Function(function)) => {
+                let bound = function_bound(ctx, scope, function, "Fn");
+                transpile!(ctx, scope, "{}: impl {bound}", self.identifier)
+            }
+            // Closures that are passed as mut can modify the variables they capture
+            (Some(DeclModifier::Mut(_)), TypeElement::Function(function)) => {
+                let bound = function_bound(ctx, scope, function, "FnMut");
+                transpile!(ctx, scope, "mut {}: impl {bound}", self.identifier)
+            }
+            // Omitted arguments are passed as `None` and replaced by the default value in the function body
+            (Some(DeclModifier::Let(_)) | None, _) if self.default_value.is_some() => {
+                let ty = borrowed_param_type(ctx, scope, self);
+                transpile!(ctx, scope, "{}: Option<{ty}>", self.identifier)
+            }
+            (Some(DeclModifier::Let(_)) | None, _) => {
                 if is_self {
                     "&self".into()
                 } else {
                     transpile_type!(self, ctx, scope, TypeOwnership::Borrowed, "&", "")
                 }
             }
-            Some(DeclModifier::Mut(_)) => {
+            (Some(DeclModifier::Mut(_)), _) => {
                 if is_self {
                     "&mut self".into()
                 } else {
                     transpile_type!(self, ctx, scope, TypeOwnership::MutBorrowed, "&mut")
                 }
             }
-            Some(DeclModifier::Ref(_)) => {
+            (Some(DeclModifier::Ref(_)), _) => {
                 if is_self {
                     panic!("Functions with ref-receivers should be handled elsewhere!")
                 }
@@ -120,3 +328,45 @@ impl Transpile for Param {
         }
     }
 }
+
+/// Type of an immutable parameter, values that are not copied are borrowed
+fn borrowed_param_type(ctx: &Context, scope: &mut Scope, param: &Param) -> String {
+    use crate::transpile_item::ident::TranspileType;
+    match &param.param_type {
+        TypeElement::Plain(plain) if ctx.mapping.is_copy(&plain.ident) => plain
+            .ident
+            .transpile_type(ctx, scope, TypeOwnership::Borrowed),
+        TypeElement::Plain(plain) => format!(
+            "&{}",
+            plain
+                .ident
+                .transpile_type(ctx, scope, TypeOwnership::Borrowed)
+        ),
+        other => format!("&{}", other.transpile(ctx, scope)),
+    }
+}
+
+/// Default values are evaluated in the called function, so they must have the type of their parameter
+fn check_default_value(scope: &Scope, param: &Param, default_value: &Expression) {
+    let is_immutable = matches!(param.decl_modifier, Some(DeclModifier::Let(_)) | None);
+    let is_function = matches!(param.param_type, TypeElement::Function(_));
+    if !is_immutable || is_function || param.identifier.as_str() == "self" {
+        todo!(
+            "TRANSPILER ERROR: `{}` cannot have a default value, only immutable parameters that are not functions can",
+            param.identifier
+        )
+    }
+    if let (TypeElement::Plain(expected), Some(TypeElement::Plain(actual))) =
+        (&param.param_type, default_value.infer_type(scope))
+    {
+        // Number literals are converted to the expected numeric type by Rust
+        if expected.ident != actual.ident && !actual.ident.as_str().starts_with("__") {
+            todo!(
+                "TRANSPILER ERROR: The default value of `{}` must be {}, but is {}",
+                param.identifier,
+                expected.ident,
+                actual.ident
+            )
+        }
+    }
+}
@@ -1,16 +1,21 @@
 use crate::builtins::BORROWED_ITERATOR_FNS;
 use crate::context::Context;
 use crate::macros::transpile;
-use crate::transpile_item::closure::transpile_closure;
+use crate::transpile_item::closure::{
+    declare_try_value, transpile_closure, transpile_typed_closure,
+};
+use crate::transpile_item::fn_decl::function_name;
+use crate::transpile_item::r#type::{is_union_value, transpile_typed_value};
 use crate::transpile_item::statement::match_ident;
 use crate::type_inference::InferType;
 use crate::Transpile;
 use galvan_ast::TypeElement::Plain;
 use galvan_ast::{
-    ComparisonOperator, DeclModifier, Expression, FunctionCall, FunctionCallArg, InfixOperator,
-    OperatorTree, Ownership, SingleExpression,
+    Block, Closure, ComparisonOperator, DeclModifier, Expression, FnDecl, FnSignature,
+    FunctionCall, FunctionCallArg, Ident, InfixOperator, OperatorTree, Ownership, Param,
+    SingleExpression, ToplevelItem, TypeElement,
 };
-use galvan_resolver::Scope;
+use galvan_resolver::{Lookup, Scope};
 use itertools::Itertools;
 
 impl Transpile for FunctionCall {
@@ -30,6 +35,7 @@ impl Transpile for FunctionCall {
                 Some(FunctionCallArg {
                     modifier,
                     expression: Expression::OperatorTree(comp),
+                    ..
                 }) => {
                     if modifier.is_some() {
                         todo!("TRANSPILER ERROR: assert modifier is not allowed for comparison operations")
@@ -75,6 +81,27 @@ impl Transpile for FunctionCall {
                     self.arguments
                 ),
             },
+            // `if` is transpiled to a Rust if, so `break`, `continue` and `return` work in its body
+            "if" => {
+                let (condition, body) = if_parts(self);
+                transpile!(
+                    ctx,
+                    scope,
+                    "if {} {{ Some({}) }} else {{ None }}",
+                    condition,
+                    body
+                )
+            }
+            // `try` is transpiled to a match, so its body can exit the surrounding function or loop
+            "try" => {
+                let (fallible, closure) = try_parts(self);
+                let fallible_type = fallible.infer_type(scope);
+                let receiver = fallible.transpile(ctx, scope);
+                let mut body_scope = Scope::child(scope);
+                let value = declare_try_value(ctx, &mut body_scope, closure, &fallible_type);
+                let body = closure.block.transpile(ctx, &mut body_scope);
+                format!("match ({receiver}).__borrow_inner() {{ Some({value}) => Some({body}), None => None }}")
+            }
             s if BORROWED_ITERATOR_FNS.contains(&s) => {
                 let ident = self.identifier.transpile(ctx, scope);
                 let args = self
@@ -94,14 +121,201 @@ impl Transpile for FunctionCall {
                 format!("{}({})", ident, args)
             }
             _ => {
-                // TODO: Resolve function and check argument types + check if they should be submitted as &, &mut or Arc<Mutex>
-                let ident = self.identifier.transpile(ctx, scope);
-                format!("{}({})", ident, self.arguments.transpile(ctx, scope))
+                let func = ctx
+                    .lookup
+                    .resolve_function(None, &self.identifier, &self.labels());
+                transpile_call(ctx, scope, self, func)
             }
         }
     }
 }
 
+/// Transpiles a call of the function that was resolved for its name, labels and - for member calls - the receiver type
+///
+/// The arguments are matched to the parameters of the resolved function, so they can be passed as the parameters expect.
+/// Calls without a resolved function are passed to Rust unchanged.
+pub(crate) fn transpile_call(
+    ctx: &Context,
+    scope: &mut Scope,
+    call: &FunctionCall,
+    func: Option<&ToplevelItem<FnDecl>>,
+) -> String {
+    // TODO: Check argument types + check if they should be submitted as &, &mut or Arc<Mutex>
+    let labels = call.labels();
+    let is_async = func.is_some_and(|func| func.signature.asyncness.is_async());
+    let await_suffix = await_suffix(scope, call, is_async);
+    if let Some(func) = func.filter(|func| !func.signature.constness.is_const()) {
+        if scope
+            .get_function()
+            .is_some_and(|function| function.is_const)
+        {
+            todo!(
+                "TRANSPILER ERROR: The function `{}` is not const and cannot be called from const functions",
+                func.signature.identifier
+            )
+        }
+    }
+    let (ident, args) = match func {
+        Some(func) => (
+            function_name(ctx, scope, &func.signature),
+            call_arguments(&func.signature, call),
+        ),
+        None if labels.is_empty() => (
+            call.identifier.transpile(ctx, scope),
+            call.arguments.iter().map(|arg| (None, Some(arg))).collect(),
+        ),
+        None => todo!(
+            "TRANSPILER ERROR: No function `{}` with the labels {} found",
+            call.identifier,
+            labels.join(", ")
+        ),
+    };
+    let args = args
+        .iter()
+        .map(|(param, arg)| {
+            // Parameters with a default value are optional, the called function fills in omitted arguments
+            let Some(arg) = arg else {
+                return "None".to_owned();
+            };
+            let value = transpile_argument(ctx, scope, *param, arg);
+            match param {
+                Some(Param {
+                    default_value: Some(_),
+                    ..
+                }) => format!("Some({value})"),
+                _ => value,
+            }
+        })
+        .join(", ");
+    format!("{ident}({args}){await_suffix}")
+}
+
+/// Async functions are awaited implicitly, which is only possible in other async functions
+pub(crate) fn await_suffix(scope: &Scope, call: &FunctionCall, is_async: bool) -> &'static str {
+    if !is_async {
+        return "";
+    }
+    if !scope
+        .get_function()
+        .is_some_and(|function| function.is_async)
+    {
+        todo!(
+            "TRANSPILER ERROR: The async function `{}` can only be called from async functions, async main or async tests",
+            call.identifier
+        )
+    }
+    ".await"
+}
+
+/// Transpiles an argument as its parameter expects it, unions are wrapped and closures take the parameter types
+fn transpile_argument(
+    ctx: &Context,
+    scope: &mut Scope,
+    param: Option<&Param>,
+    arg: &FunctionCallArg,
+) -> String {
+    match param {
+        Some(Param { param_type, .. })
+            if arg.modifier.is_none() && is_union_value(param_type, &arg.expression) =>
+        {
+            format!(
+                "&{}",
+                transpile_typed_value(ctx, scope, param_type, &arg.expression)
+            )
+        }
+        Some(Param {
+            identifier,
+            param_type: TypeElement::Function(function),
+            ..
+        }) => match (&arg.modifier, &arg.expression) {
+            (None | Some(DeclModifier::Mut(_)), Expression::Closure(closure)) => {
+                transpile_typed_closure(ctx, scope, closure, function, identifier)
+            }
+            // Functions can be passed by name
+            (None, match_ident!(ident)) if scope.get_variable(ident).is_none() => {
+                match ctx.lookup.resolve_function(None, ident, &[]) {
+                    Some(func) => function_name(ctx, scope, &func.signature),
+                    None => arg.transpile(ctx, scope),
+                }
+            }
+            _ => arg.transpile(ctx, scope),
+        },
+        _ => arg.transpile(ctx, scope),
+    }
+}
+
+/// Matches the arguments of a call to the parameters of the called function
+///
+/// Labeled parameters take the argument with their label, the others take the unlabeled arguments in order.
+/// Arguments are only omitted for parameters with a default value.
+fn call_arguments<'a>(
+    signature: &'a FnSignature,
+    call: &'a FunctionCall,
+) -> Vec<(Option<&'a Param>, Option<&'a FunctionCallArg>)> {
+    // The receiver of a member function is not passed as an argument
+    let params = &signature.parameters.params[signature.receiver().iter().count()..];
+    let mut positional = call.arguments.iter().filter(|arg| arg.label.is_none());
+    let arguments = params
+        .iter()
+        .map(|param| {
+            let arg = match &param.label {
+                Some(label) => call
+                    .arguments
+                    .iter()
+                    .find(|arg| arg.label.as_ref() == Some(label)),
+                None => positional.next(),
+            };
+            if arg.is_none() && param.default_value.is_none() {
+                todo!(
+                    "TRANSPILER ERROR: Missing argument `{}` in call of `{}`",
+                    param.label.as_ref().unwrap_or(&param.identifier),
+                    call.identifier
+                )
+            }
+            (Some(param), arg)
+        })
+        .collect();
+    if positional.next().is_some() {
+        todo!(
+            "TRANSPILER ERROR: Too many arguments in call of `{}`",
+            call.identifier
+        )
+    }
+    arguments
+}
+
+/// Splits an `if` call into its condition and its body
+pub(crate) fn if_parts(call: &FunctionCall) -> (&Expression, &Block) {
+    match call.arguments.as_slice() {
+        [FunctionCallArg {
+            label: None,
+            modifier: None,
+            expression: condition,
+        }, FunctionCallArg {
+            label: None,
+            modifier: None,
+            expression: Expression::Closure(closure),
+        }] if closure.arguments.is_empty() => (condition, &closure.block),
+        _ => todo!("TRANSPILER ERROR: if expects a condition followed by a body"),
+    }
+}
+
+/// Splits a `try` call into the optional or result and the body that receives the unwrapped value
+pub(crate) fn try_parts(call: &FunctionCall) -> (&Expression, &Closure) {
+    match call.arguments.as_slice() {
+        [FunctionCallArg {
+            label: None,
+            modifier: None,
+            expression: fallible,
+        }, FunctionCallArg {
+            label: None,
+            modifier: None,
+            expression: Expression::Closure(closure),
+        }] => (fallible, closure),
+        _ => todo!("TRANSPILER ERROR: try expects an optional or result followed by a body"),
+    }
+}
+
 impl Transpile for FunctionCallArg {
     fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
         use DeclModifier as Mod;
@@ -109,49 +323,21 @@ impl Transpile for FunctionCallArg {
         let Self {
             modifier,
             expression,
+            ..
         } = self;
         match (modifier, expression) {
             (Some(Mod::Let(_)), _) => {
                 todo!("TRANSPILER ERROR: Let modifier is not allowed for function call arguments")
             }
             (None, match_ident!(ident)) => {
-                match scope
-                    .get_variable(ident)
-                    .unwrap_or_else(|| {
-                        panic!(
-                            "TODO: ERROR: undeclared variable {ident}, scope: {:#?}",
-                            scope
-                        )
-                    })
-                    .ownership
-                {
-                    Ownership::Owned => {
-                        transpile!(ctx, scope, "&{}", ident)
-                    }
-                    Ownership::Borrowed | Ownership::MutBorrowed | Ownership::Copy => {
-                        transpile!(ctx, scope, "{}", ident)
-                    }
-                    Ownership::Ref => {
-                        transpile!(ctx, scope, "{}.lock().unwrap()", ident)
-                    }
-                }
+                transpile_value_argument(ctx, scope, expression, Some(ident), None)
             }
             (None, Exp::Closure(closure)) => {
                 transpile!(ctx, scope, "{}", closure)
             }
             (None, expression) => {
-                let t = expression.infer_type(scope);
-                if t.is_some_and(|t| {
-                    if let Plain(plain) = t {
-                        ctx.mapping.is_copy(&plain.ident)
-                    } else {
-                        false
-                    }
-                }) {
-                    transpile!(ctx, scope, "{}", expression)
-                } else {
-                    transpile!(ctx, scope, "&({})", expression)
-                }
+                let ty = expression.infer_type(scope);
+                transpile_value_argument(ctx, scope, expression, None, ty)
             }
             (Some(Mod::Mut(_)), expr @ Exp::MemberChain(_) | expr @ match_ident!(_)) => {
                 transpile!(ctx, scope, "&mut {}", expr)
@@ -163,3 +349,50 @@ impl Transpile for FunctionCallArg {
         }
     }
 }
+
+/// Passes an argument without modifier, values are borrowed unless their type is copy
+///
+/// `ident` is set if the argument is a variable, its ownership then decides how it is passed
+pub(crate) fn transpile_value_argument(
+    ctx: &Context,
+    scope: &mut Scope,
+    value: &impl Transpile,
+    ident: Option<&Ident>,
+    ty: Option<TypeElement>,
+) -> String {
+    if let Some(ident) = ident {
+        return match scope
+            .get_variable(ident)
+            .unwrap_or_else(|| {
+                panic!(
+                    "TODO: ERROR: undeclared variable {ident}, scope: {:#?}",
+                    scope
+                )
+            })
+            .ownership
+        {
+            Ownership::Owned => {
+                transpile!(ctx, scope, "&{}", ident)
+            }
+            Ownership::Borrowed | Ownership::MutBorrowed | Ownership::Copy => {
+                transpile!(ctx, scope, "{}", ident)
+            }
+            Ownership::Ref => {
+                transpile!(ctx, scope, "{}.lock().unwrap()", ident)
+            }
+        };
+    }
+
+    if ty.is_some_and(|t| {
+        if let Plain(plain) = t {
+            // Number literals are always copy
+            ctx.mapping.is_copy(&plain.ident) || plain.ident.as_str() == "__Number"
+        } else {
+            false
+        }
+    }) {
+        transpile!(ctx, scope, "{}", value)
+    } else {
+        transpile!(ctx, scope, "&({})", value)
+    }
+}
@@ -4,28 +4,26 @@ use crate::{Ident, Transpile, TypeIdent};
 use galvan_resolver::Scope;
 
 impl Transpile for Ident {
-    fn transpile(&self, _ctx: &Context, _scope: &mut Scope) -> String {
+    fn transpile(&self, _ctx: &Context, scope: &mut Scope) -> String {
+        let name = self.as_str();
+        if name == "it" || name.starts_with('#') {
+            scope.use_implicit_parameter(self);
+        }
         // TODO: Escape ident when name has collision with rust keyword
         // TODO: Use lookup to insert fully qualified name
-        sanitize_name(self.as_str()).into()
+        sanitize_name(name).into()
     }
 }
 
 impl Transpile for TypeIdent {
-    fn transpile(&self, ctx: &Context, _scope: &mut Scope) -> String {
-        let Some(_decl) = ctx.lookup.types.get(self) else {
-            todo!("Handle type resolving errors. Type {} not found", self);
-        };
-        // TODO: Handle module path here and use fully qualified name
-        let name = ctx.mapping.get_owned(self);
-        format!("{name}")
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        self.transpile_type(ctx, scope, TypeOwnership::Owned)
     }
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum TypeOwnership {
     Owned,
-    MutOwned,
     Borrowed,
     MutBorrowed,
 }
@@ -42,7 +40,6 @@ impl TranspileType for TypeIdent {
         // TODO: Handle module path here and use fully qualified name
         let name = match ownership {
             TypeOwnership::Owned => ctx.mapping.get_owned(self),
-            TypeOwnership::MutOwned => todo!("Transpile mutable owned types"), // ctx.mapping.get_mut_owned(&self),
             TypeOwnership::Borrowed => ctx.mapping.get_borrowed(self),
             TypeOwnership::MutBorrowed => ctx.mapping.get_mut_borrowed(self),
         };
@@ -0,0 +1,194 @@
+use crate::context::Context;
+use crate::macros::transpile;
+use crate::transpile_item::statement::{
+    match_ident, transpile_body, transpile_owned_expression, BodyResult,
+};
+use crate::type_inference::infer_element_type;
+use crate::Transpile;
+use galvan_ast::{
+    Break, Continue, DeclModifier, Expression, ForLoop, Ident, Label, LetKeyword, LoopExpression,
+    Ownership, RangeExpression, RangeKind, SingleExpression, TypeElement, WhileLoop,
+};
+use galvan_resolver::{LoopContext, Scope, Variable};
+use itertools::Itertools;
+
+impl Transpile for RangeExpression {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        let operator = match self.kind {
+            RangeKind::Exclusive => "..",
+            RangeKind::Inclusive => "..=",
+        };
+        transpile!(ctx, scope, "{}{operator}{}", self.start, self.end)
+    }
+}
+
+impl Transpile for ForLoop {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        let element_type = infer_element_type(&self.iterable, scope);
+        let is_copy = matches!(
+            &element_type,
+            Some(TypeElement::Plain(plain)) if ctx.mapping.is_copy(&plain.ident)
+        );
+
+        let (iterable, ownership) = match &self.iterable {
+            Expression::RangeExpression(range) => (range.transpile(ctx, scope), Ownership::Copy),
+            // Collections are iterated by reference, copyable elements are copied out of the collection
+            iterable => {
+                let iterable = transpile_iterable(ctx, scope, iterable);
+                if is_copy {
+                    (format!("{iterable}.iter().copied()"), Ownership::Copy)
+                } else {
+                    (format!("{iterable}.iter()"), Ownership::Borrowed)
+                }
+            }
+        };
+
+        let label = transpile_loop_label(ctx, scope, &self.label);
+        let mut loop_scope = Scope::loop_body(scope, loop_context(&self.label, false));
+        let scope = &mut loop_scope;
+        let pattern = declare_loop_variables(ctx, scope, self, element_type, ownership);
+        let body = transpile_body(ctx, scope, &self.body.body, BodyResult::Discarded);
+        format!("{label}for {pattern} in {iterable} {body}")
+    }
+}
+
+impl Transpile for WhileLoop {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        let label = transpile_loop_label(ctx, scope, &self.label);
+        let condition = self.condition.transpile(ctx, scope);
+        let mut loop_scope = Scope::loop_body(scope, loop_context(&self.label, false));
+        let body = transpile_body(ctx, &mut loop_scope, &self.body.body, BodyResult::Discarded);
+        format!("{label}while {condition} {body}")
+    }
+}
+
+impl Transpile for LoopExpression {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        let label = transpile_loop_label(ctx, scope, &self.label);
+        let mut loop_scope = Scope::loop_body(scope, loop_context(&self.label, true));
+        let body = transpile_body(ctx, &mut loop_scope, &self.body.body, BodyResult::Discarded);
+        format!("{label}loop {body}")
+    }
+}
+
+impl Transpile for Break {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        let Some(context) = scope.get_loop(self.label.as_ref()) else {
+            match &self.label {
+                Some(label) => todo!(
+                    "TRANSPILER ERROR: Loop label '{} is not declared",
+                    label.ident
+                ),
+                None => todo!("TRANSPILER ERROR: break is only allowed inside of a loop"),
+            }
+        };
+        if self.value.is_some() && !context.yields_value {
+            todo!("TRANSPILER ERROR: Only `loop` can be exited with a value, `for` and `while` loops cannot")
+        }
+
+        let label = self
+            .label
+            .as_ref()
+            .map_or(String::new(), |label| transpile!(ctx, scope, " {}", label));
+        let value = self.value.as_ref().map_or(String::new(), |value| {
+            format!(" {}", transpile_owned_expression(ctx, value, scope))
+        });
+        format!("break{label}{value}")
+    }
+}
+
+impl Transpile for Continue {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        if scope.get_loop(self.label.as_ref()).is_none() {
+            match &self.label {
+                Some(label) => todo!(
+                    "TRANSPILER ERROR: Loop label '{} is not declared",
+                    label.ident
+                ),
+                None => todo!("TRANSPILER ERROR: continue is only allowed inside of a loop"),
+            }
+        }
+
+        let label = self
+            .label
+            .as_ref()
+            .map_or(String::new(), |label| transpile!(ctx, scope, " {}", label));
+        format!("continue{label}")
+    }
+}
+
+impl Transpile for Label {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        transpile!(ctx, scope, "'{}", self.ident)
+    }
+}
+
+fn transpile_loop_label(ctx: &Context, scope: &mut Scope, label: &Option<Label>) -> String {
+    label
+        .as_ref()
+        .map_or(String::new(), |label| transpile!(ctx, scope, "{}: ", label))
+}
+
+fn loop_context(label: &Option<Label>, yields_value: bool) -> LoopContext {
+    LoopContext {
+        label: label.clone(),
+        yields_value,
+    }
+}
+
+fn transpile_iterable(ctx: &Context, scope: &mut Scope, iterable: &Expression) -> String {
+    match iterable {
+        match_ident!(ident) => match scope.get_variable(ident).map(|var| var.ownership) {
+            Some(Ownership::Ref) => transpile!(ctx, scope, "{}.lock().unwrap()", ident),
+            _ => ident.transpile(ctx, scope),
+        },
+        Expression::MemberChain(chain) => chain.transpile(ctx, scope),
+        iterable => transpile!(ctx, scope, "({})", iterable),
+    }
+}
+
+/// Declares the loop variables in the scope of the loop body and returns the pattern that binds them
+fn declare_loop_variables(
+    ctx: &Context,
+    scope: &mut Scope,
+    for_loop: &ForLoop,
+    element_type: Option<TypeElement>,
+    ownership: Ownership,
+) -> String {
+    let mut declare = |ident: Ident, ty: Option<TypeElement>| {
+        scope.declare_variable(Variable {
+            ident,
+            modifier: DeclModifier::Let(LetKeyword),
+            ty,
+            ownership,
+        })
+    };
+
+    match for_loop.arguments.as_slice() {
+        [] => {
+            declare(Ident::new("it"), element_type);
+            "it".into()
+        }
+        [arg] => {
+            declare(arg.ident.clone(), arg.ty.clone().or(element_type));
+            transpile!(ctx, scope, "{}", arg.ident)
+        }
+        // Multiple loop variables destructure the elements e.g.: `for dict |key, value| { ... }`
+        args => {
+            let element_types = match element_type {
+                Some(TypeElement::Tuple(tuple)) if tuple.elements.len() == args.len() => {
+                    tuple.elements.into_iter().map(Some).collect_vec()
+                }
+                _ => vec![None; args.len()],
+            };
+            for (arg, ty) in args.iter().zip(element_types) {
+                declare(arg.ident.clone(), arg.ty.clone().or(ty));
+            }
+            let idents = args
+                .iter()
+                .map(|arg| arg.ident.transpile(ctx, scope))
+                .join(", ");
+            format!("({idents})")
+        }
+    }
+}
@@ -0,0 +1,544 @@
+use crate::context::Context;
+use crate::macros::transpile;
+use crate::transpile_item::r#enum::{resolve_enum, variant_enum_name, VARIANT_FIELD};
+use crate::type_inference::InferType;
+use crate::Transpile;
+use galvan_ast::{
+    DeclModifier, EnumPattern, EnumTypeDecl, EnumTypeVariant, EnumVariantFields, Expression, Ident,
+    LetKeyword, Literal, MatchArm, MatchExpression, Ownership, Pattern, RefKeyword,
+    SingleExpression, TypeElement,
+};
+use galvan_resolver::{Scope, Variable};
+use itertools::Itertools;
+
+impl Transpile for MatchExpression {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        check_exhaustive(ctx, self);
+
+        let subject_type = self.subject.infer_type(scope);
+        let arms = self
+            .arms
+            .iter()
+            .map(|arm| transpile_arm(ctx, scope, arm, subject_type.as_ref()))
+            .join(",\n");
+        // Bindings in the arms are owned, so they must not move out of a variable or field
+        if is_place(&self.subject) && self.arms.iter().any(|arm| binds_value(&arm.pattern)) {
+            transpile!(
+                ctx,
+                scope,
+                "match ({}).to_owned() {{\n{arms}\n}}",
+                self.subject
+            )
+        } else {
+            transpile!(ctx, scope, "match {} {{\n{arms}\n}}", self.subject)
+        }
+    }
+}
+
+/// Returns true if the expression refers to an existing value instead of creating a new one
+fn is_place(expression: &Expression) -> bool {
+    match expression {
+        Expression::SingleExpression(SingleExpression::Ident(_))
+        | Expression::SingleExpression(SingleExpression::Postfix(_)) => true,
+        Expression::MemberChain(chain) => chain.is_field(),
+        _ => false,
+    }
+}
+
+/// Returns true if matching the pattern binds (part of) the subject to a variable
+fn binds_value(pattern: &Pattern) -> bool {
+    match pattern {
+        // String literals are compared in a guard, so the value is bound as well
+        Pattern::Binding(_) | Pattern::Literal(Literal::StringLiteral(_)) => true,
+        Pattern::Tuple(elements) => elements.iter().any(binds_value),
+        Pattern::Enum(pattern) => pattern.fields.iter().any(|f| binds_value(&f.pattern)),
+        Pattern::Some(inner) => binds_value(inner),
+        Pattern::Wildcard | Pattern::Literal(_) | Pattern::None => false,
+    }
+}
+
+fn transpile_arm(
+    ctx: &Context,
+    scope: &mut Scope,
+    arm: &MatchArm,
+    subject_type: Option<&TypeElement>,
+) -> String {
+    let mut arm_scope = Scope::child(scope);
+    let scope = &mut arm_scope;
+
+    // Patterns that cannot be expressed in Rust are turned into additional guard conditions
+    let mut conditions = vec![];
+    let pattern = transpile_pattern(ctx, scope, &arm.pattern, subject_type, &mut conditions);
+    if let Some(guard) = &arm.guard {
+        conditions.push(guard.transpile(ctx, scope));
+    }
+
+    let guard = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!(
+            " if {}",
+            conditions.iter().map(|c| format!("({c})")).join(" && ")
+        )
+    };
+    let body = arm.body.transpile(ctx, scope);
+    format!("{pattern}{guard} => {body}")
+}
+
+fn transpile_pattern(
+    ctx: &Context,
+    scope: &mut Scope,
+    pattern: &Pattern,
+    ty: Option<&TypeElement>,
+    conditions: &mut Vec<String>,
+) -> String {
+    match pattern {
+        Pattern::Wildcard => "_".into(),
+        Pattern::Binding(ident) => declare_binding(ctx, scope, ident, ty.cloned(), false),
+        Pattern::Literal(Literal::StringLiteral(string)) => {
+            // Rust cannot match owned strings against string literals
+            let binding = format!("__match_{}", conditions.len());
+            conditions.push(format!("{binding} == {}", string.as_str()));
+            binding
+        }
+        Pattern::Literal(literal) => literal.transpile(ctx, scope),
+        Pattern::Tuple(elements) => {
+            let element_types = match ty {
+                Some(TypeElement::Tuple(tuple)) if tuple.elements.len() == elements.len() => {
+                    tuple.elements.iter().map(Some).collect()
+                }
+                _ => vec![None; elements.len()],
+            };
+            let trailing_comma = if elements.len() == 1 { "," } else { "" };
+            let elements = elements
+                .iter()
+                .zip(element_types)
+                .map(|(element, ty)| transpile_pattern(ctx, scope, element, ty, conditions))
+                .join(", ");
+            format!("({elements}{trailing_comma})")
+        }
+        Pattern::Some(inner) => {
+            let inner_type = match ty {
+                Some(TypeElement::Optional(optional)) => Some(&optional.some),
+                _ => None,
+            };
+            let inner = transpile_pattern(ctx, scope, inner, inner_type, conditions);
+            format!("Some({inner})")
+        }
+        Pattern::None => "None".into(),
+        Pattern::Enum(pattern) => transpile_enum_pattern(ctx, scope, pattern, conditions),
+    }
+}
+
+fn transpile_enum_pattern(
+    ctx: &Context,
+    scope: &mut Scope,
+    pattern: &EnumPattern,
+    conditions: &mut Vec<String>,
+) -> String {
+    let decl = resolve_enum(ctx, &pattern.enum_ident);
+    let Some(variant) = decl.variant(&pattern.variant) else {
+        todo!(
+            "TRANSPILER ERROR: Enum {} has no variant {}",
+            pattern.enum_ident,
+            pattern.variant
+        )
+    };
+
+    let (shared, fields): (Vec<_>, Vec<_>) = pattern.fields.iter().partition(|field| {
+        field
+            .ident
+            .as_ref()
+            .is_some_and(|ident| decl.fields.iter().any(|f| f.ident == *ident))
+    });
+
+    let enum_name = if decl.has_shared_fields() {
+        variant_enum_name(&pattern.enum_ident)
+    } else {
+        pattern.enum_ident.transpile(ctx, scope)
+    };
+    let ident = variant.ident.as_str();
+    let variant_pattern = match &variant.fields {
+        EnumVariantFields::Unit if fields.is_empty() => format!("{enum_name}::{ident}"),
+        EnumVariantFields::Unit => {
+            todo!("TRANSPILER ERROR: Variant {ident} does not have any fields")
+        }
+        _ if fields.is_empty() => format!("{enum_name}::{ident} {{ .. }}"),
+        EnumVariantFields::Tuple(members) => {
+            if fields.len() > members.len() || fields.iter().any(|f| f.ident.is_some()) {
+                todo!(
+                    "TRANSPILER ERROR: Variant {ident} expects up to {} unnamed fields",
+                    members.len()
+                )
+            }
+            let rest = if fields.len() < members.len() {
+                ", .."
+            } else {
+                ""
+            };
+            let fields = fields
+                .iter()
+                .zip(members)
+                .map(|(field, member)| {
+                    transpile_pattern(ctx, scope, &field.pattern, Some(&member.r#type), conditions)
+                })
+                .join(", ");
+            format!("{enum_name}::{ident}({fields}{rest})")
+        }
+        EnumVariantFields::Struct(members) => {
+            let fields = fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    // Unnamed fields are matched in declaration order
+                    let member = match &field.ident {
+                        Some(name) => members.iter().find(|m| m.ident == *name),
+                        None => members.get(i),
+                    };
+                    let Some(member) = member else {
+                        todo!("TRANSPILER ERROR: Variant {ident} does not have this field")
+                    };
+                    let is_ref = matches!(member.decl_modifier, Some(DeclModifier::Ref(_)));
+                    let pattern = transpile_field_pattern(
+                        ctx,
+                        scope,
+                        &field.pattern,
+                        &member.r#type,
+                        is_ref,
+                        conditions,
+                    );
+                    transpile!(ctx, scope, "{}: {pattern}", member.ident)
+                })
+                .join(", ");
+            format!("{enum_name}::{ident} {{ {fields}, .. }}")
+        }
+    };
+
+    if !decl.has_shared_fields() {
+        return variant_pattern;
+    }
+
+    let shared = shared
+        .iter()
+        .map(|field| {
+            let member = decl
+                .fields
+                .iter()
+                .find(|f| Some(&f.ident) == field.ident.as_ref())
+                .expect("Shared field should exist");
+            let is_ref = matches!(member.decl_modifier, Some(DeclModifier::Ref(_)));
+            let pattern = transpile_field_pattern(
+                ctx,
+                scope,
+                &field.pattern,
+                &member.r#type,
+                is_ref,
+                conditions,
+            );
+            transpile!(ctx, scope, "{}: {pattern}, ", member.ident)
+        })
+        .join("");
+    transpile!(
+        ctx,
+        scope,
+        "{} {{ {shared}{VARIANT_FIELD}: {variant_pattern}, .. }}",
+        pattern.enum_ident
+    )
+}
+
+fn transpile_field_pattern(
+    ctx: &Context,
+    scope: &mut Scope,
+    pattern: &Pattern,
+    ty: &TypeElement,
+    is_ref: bool,
+    conditions: &mut Vec<String>,
+) -> String {
+    match pattern {
+        Pattern::Binding(ident) => declare_binding(ctx, scope, ident, Some(ty.clone()), is_ref),
+        Pattern::Wildcard => "_".into(),
+        _ if is_ref => {
+            todo!("TRANSPILER ERROR: ref fields can only be bound to a variable in a pattern")
+        }
+        pattern => transpile_pattern(ctx, scope, pattern, Some(ty), conditions),
+    }
+}
+
+fn declare_binding(
+    ctx: &Context,
+    scope: &mut Scope,
+    ident: &Ident,
+    ty: Option<TypeElement>,
+    is_ref: bool,
+) -> String {
+    let (modifier, ownership) = match &ty {
+        _ if is_ref => (DeclModifier::Ref(RefKeyword), Ownership::Ref),
+        Some(TypeElement::Plain(plain)) if ctx.mapping.is_copy(&plain.ident) => {
+            (DeclModifier::Let(LetKeyword), Ownership::Copy)
+        }
+        _ => (DeclModifier::Let(LetKeyword), Ownership::Owned),
+    };
+    scope.declare_variable(Variable {
+        ident: ident.clone(),
+        modifier,
+        ty,
+        ownership,
+    });
+    ident.transpile(ctx, scope)
+}
+
+/// Checks that every possible value of the subject is matched by an arm
+///
+/// This is checked on the Galvan patterns, as rustc reports missing arms for the generated code,
+/// where string literals are compared in guards and enums with shared fields are wrapped in a struct
+fn check_exhaustive(ctx: &Context, expr: &MatchExpression) {
+    // Only arms without a guard count towards exhaustiveness
+    let rows = expr
+        .arms
+        .iter()
+        .filter(|arm| arm.guard.is_none())
+        .map(|arm| vec![&arm.pattern])
+        .collect_vec();
+
+    let missing = missing_patterns(ctx, rows, 1);
+    if !missing.is_empty() {
+        todo!(
+            "TRANSPILER ERROR: Non-exhaustive match, missing arms for: {}",
+            missing.iter().map(|witness| &witness[0]).join(", ")
+        )
+    }
+}
+
+static WILDCARD: Pattern = Pattern::Wildcard;
+
+/// Finds values that are not matched by any row of patterns, where each row matches `width` values.
+/// Every missing value is returned as one pattern per column.
+fn missing_patterns<'a>(
+    ctx: &'a Context,
+    rows: Vec<Vec<&'a Pattern>>,
+    width: usize,
+) -> Vec<Vec<String>> {
+    if width == 0 {
+        return if rows.is_empty() {
+            vec![vec![]]
+        } else {
+            vec![]
+        };
+    }
+
+    let column = rows.iter().map(|row| row[0]).collect_vec();
+    let Some(constructors) = constructors(ctx, &column) else {
+        // Values that cannot be listed exhaustively are only covered by irrefutable patterns
+        let rows = rows
+            .iter()
+            .filter(|row| matches!(row[0], Pattern::Wildcard | Pattern::Binding(_)))
+            .map(|row| row[1..].to_vec())
+            .collect_vec();
+        return missing_patterns(ctx, rows, width - 1)
+            .into_iter()
+            .map(|witness| [vec!["_".to_owned()], witness].concat())
+            .collect();
+    };
+
+    constructors
+        .iter()
+        .flat_map(|constructor| {
+            let arity = constructor.arity();
+            let rows = rows
+                .iter()
+                .filter_map(|row| Some([constructor.fields(row[0])?, row[1..].to_vec()].concat()))
+                .collect_vec();
+            missing_patterns(ctx, rows, arity + width - 1)
+                .into_iter()
+                .map(move |mut witness| {
+                    let rest = witness.split_off(arity);
+                    [vec![constructor.display(&witness)], rest].concat()
+                })
+        })
+        .collect()
+}
+
+/// The ways to construct a value that patterns can distinguish
+enum Constructor<'a> {
+    Variant(&'a EnumTypeDecl, &'a EnumPattern, &'a EnumTypeVariant),
+    Some,
+    None,
+    Bool(bool),
+    Tuple(usize),
+}
+
+/// Lists all constructors of the type matched by the column, if they can be listed exhaustively
+fn constructors<'a>(ctx: &'a Context, column: &[&'a Pattern]) -> Option<Vec<Constructor<'a>>> {
+    column.iter().find_map(|pattern| match pattern {
+        Pattern::Enum(pattern) => {
+            let decl = resolve_enum(ctx, &pattern.enum_ident);
+            Some(
+                decl.variants
+                    .iter()
+                    .map(|variant| Constructor::Variant(decl, pattern, variant))
+                    .collect(),
+            )
+        }
+        Pattern::Some(_) | Pattern::None => Some(vec![Constructor::Some, Constructor::None]),
+        Pattern::Literal(Literal::BooleanLiteral(_)) => {
+            Some(vec![Constructor::Bool(true), Constructor::Bool(false)])
+        }
+        Pattern::Tuple(elements) => Some(vec![Constructor::Tuple(elements.len())]),
+        _ => None,
+    })
+}
+
+impl<'a> Constructor<'a> {
+    fn arity(&self) -> usize {
+        match self {
+            Constructor::Variant(decl, _, variant) => {
+                decl.fields.len()
+                    + match &variant.fields {
+                        EnumVariantFields::Unit => 0,
+                        EnumVariantFields::Tuple(members) => members.len(),
+                        EnumVariantFields::Struct(members) => members.len(),
+                    }
+            }
+            Constructor::Some => 1,
+            Constructor::None | Constructor::Bool(_) => 0,
+            Constructor::Tuple(len) => *len,
+        }
+    }
+
+    /// The patterns for the fields of this constructor, or none if the pattern does not match it
+    fn fields(&self, pattern: &'a Pattern) -> Option<Vec<&'a Pattern>> {
+        match (self, pattern) {
+            (_, Pattern::Wildcard | Pattern::Binding(_)) => Some(vec![&WILDCARD; self.arity()]),
+            (Constructor::Variant(decl, _, variant), Pattern::Enum(pattern))
+                if pattern.variant == variant.ident =>
+            {
+                // Shared fields come first, followed by the fields of the variant
+                let mut fields = vec![&WILDCARD; self.arity()];
+                let (shared, own): (Vec<_>, Vec<_>) = pattern.fields.iter().partition(|field| {
+                    field
+                        .ident
+                        .as_ref()
+                        .is_some_and(|ident| decl.fields.iter().any(|f| f.ident == *ident))
+                });
+                for field in shared {
+                    let index = decl
+                        .fields
+                        .iter()
+                        .position(|f| Some(&f.ident) == field.ident.as_ref());
+                    if let Some(index) = index {
+                        fields[index] = &field.pattern;
+                    }
+                }
+                for (i, field) in own.into_iter().enumerate() {
+                    let index = match (&variant.fields, &field.ident) {
+                        (EnumVariantFields::Struct(members), Some(name)) => {
+                            members.iter().position(|m| m.ident == *name)
+                        }
+                        _ => Some(i),
+                    };
+                    if let Some(slot) =
+                        index.and_then(|index| fields.get_mut(decl.fields.len() + index))
+                    {
+                        *slot = &field.pattern;
+                    }
+                }
+                Some(fields)
+            }
+            (Constructor::Some, Pattern::Some(inner)) => Some(vec![inner]),
+            (Constructor::None, Pattern::None) => Some(vec![]),
+            (Constructor::Bool(value), Pattern::Literal(Literal::BooleanLiteral(b)))
+                if b.0 == *value =>
+            {
+                Some(vec![])
+            }
+            (Constructor::Tuple(_), Pattern::Tuple(elements)) => Some(elements.iter().collect()),
+            _ => None,
+        }
+    }
+
+    fn display(&self, fields: &[String]) -> String {
+        match self {
+            Constructor::Variant(_, pattern, variant) if fields.iter().all(|f| f == "_") => {
+                format!("{}::{}", pattern.enum_ident, variant.ident)
+            }
+            Constructor::Variant(_, pattern, variant) => format!(
+                "{}::{}({})",
+                pattern.enum_ident,
+                variant.ident,
+                fields.join(", ")
+            ),
+            Constructor::Some => format!("some({})", fields[0]),
+            Constructor::None => "none".to_owned(),
+            Constructor::Bool(value) => value.to_string(),
+            Constructor::Tuple(_) => format!("({})", fields.join(", ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::transpile;
+    use galvan_files::Source;
+
+    const TYPES: &str = "
+        type Light { Red, Yellow, Green }
+        type Signal {
+            Fixed(Light)
+            Blinking(Light, Int)
+            Off
+        }
+    ";
+
+    fn transpile_fn(code: &str) {
+        let source = Source::from_string(format!("{TYPES}\n{code}"));
+        transpile(vec![source]).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Non-exhaustive match, missing arms for: Light::Green")]
+    fn test_guarded_arms_are_not_exhaustive() {
+        transpile_fn(
+            "fn go(light: Light, clear: Bool) -> Bool {
+                match light {
+                    Light::Green if clear => true
+                    Light::Red => false
+                    Light::Yellow => false
+                }
+            }",
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Non-exhaustive match, missing arms for: some(Signal::Fixed(Light::Yellow)), some(Signal::Fixed(Light::Green)), some(Signal::Blinking(Light::Yellow, _)), some(Signal::Blinking(Light::Green, _))"
+    )]
+    fn test_missing_nested_variants() {
+        transpile_fn(
+            "fn stops(signal: Signal?) -> Bool {
+                match signal {
+                    some(Signal::Fixed(Light::Red)) => true
+                    some(Signal::Blinking(_, times)) if times > 2 => true
+                    some(Signal::Blinking(Light::Red, _)) => true
+                    some(Signal::Off) => false
+                    none => false
+                }
+            }",
+        );
+    }
+
+    #[test]
+    fn test_nested_variants_are_exhaustive() {
+        transpile_fn(
+            "fn stops(signal: Signal?) -> Bool {
+                match signal {
+                    some(Signal::Fixed(Light::Red)) => true
+                    some(Signal::Fixed(_)) => false
+                    some(Signal::Blinking(light, times)) if times > 2 => true
+                    some(Signal::Blinking(_, _)) => false
+                    some(Signal::Off) => false
+                    none => false
+                }
+            }",
+        );
+    }
+}
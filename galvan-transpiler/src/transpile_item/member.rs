@@ -1,19 +1,249 @@
+use crate::builtins::BORROWED_ITERATOR_FNS;
 use crate::context::Context;
-use crate::macros::{impl_transpile, transpile};
+use crate::macros::transpile;
+use crate::transpile_item::closure::transpile_function_value;
+use crate::transpile_item::function_call::{await_suffix, transpile_call};
+use crate::transpile_item::r#type::{is_union_value, transpile_typed_value};
+use crate::transpile_item::statement::transpile_owned_expression;
+use crate::type_inference::{field_function, infer, infer_member_access, InferType};
 use crate::Transpile;
-use galvan_ast::{ConstructorCall, ConstructorCallArg, MemberChain};
-use galvan_resolver::Scope;
+use galvan_ast::{
+    ConstructorCall, ConstructorCallArg, FunctionCall, MemberChain, PostfixExpression,
+    SingleExpression, TypeDecl, TypeElement,
+};
+use galvan_resolver::{Lookup, Scope};
 use itertools::Itertools;
 
 impl Transpile for MemberChain {
     fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
-        self.elements
+        transpile_member_chain(ctx, scope, None, &self.elements)
+    }
+}
+
+/// Transpiles the elements of a member chain, optionally accessed on an already transpiled receiver of the given type
+///
+/// The members after a safe call `?.` are transpiled into a closure that is only called if the receiver has a value
+fn transpile_member_chain(
+    ctx: &Context,
+    scope: &mut Scope,
+    receiver: Option<(&str, Option<TypeElement>)>,
+    elements: &[SingleExpression],
+) -> String {
+    let receiver_ident = receiver.as_ref().map(|(ident, _)| ident.to_string());
+    let Some(position) = elements.iter().position(|e| safe_call(e).is_some()) else {
+        let chain = join_members(ctx, scope, receiver.clone(), elements);
+        // Fields are borrowed from the receiver inside of a safe call
+        return match elements.last() {
+            Some(SingleExpression::Ident(_)) if receiver.is_some() => format!("{chain}.to_owned()"),
+            _ => chain,
+        };
+    };
+
+    let receiver_type = match receiver {
+        Some((_, ty)) => ty.and_then(|ty| infer_member_access(scope, ty, &elements[..=position])),
+        None => elements.split_first().and_then(|(first, rest)| {
+            infer_member_access(scope, first.infer_type(scope)?, &rest[..position])
+        }),
+    };
+    let inner_type = match receiver_type {
+        Some(TypeElement::Optional(optional)) => Some(optional.some),
+        Some(TypeElement::Result(result)) => Some(result.success),
+        _ => None,
+    };
+    let members = &elements[position + 1..];
+    let value_type = inner_type
+        .clone()
+        .and_then(|ty| infer_member_access(scope, ty, members));
+
+    let safe_receiver = safe_call(&elements[position])
+        .unwrap()
+        .transpile(ctx, scope);
+    let receiver = receiver_ident
+        .into_iter()
+        .chain(elements[..position].iter().map(|e| e.transpile(ctx, scope)))
+        .chain(std::iter::once(safe_receiver))
+        .join(".");
+    let value = transpile_member_chain(ctx, scope, Some(("__value", inner_type)), members);
+
+    // Optional values are flattened, so chained safe calls result in a single optional
+    let is_nested = members.iter().any(|member| safe_call(member).is_some());
+    let (adapter, value) = match value_type {
+        _ if is_nested => ("and_then", value),
+        Some(TypeElement::Optional(_)) => ("and_then", value),
+        Some(TypeElement::Result(_)) => ("and_then", format!("({value}).ok()")),
+        _ => ("map", value),
+    };
+    format!("({receiver}).__borrow_inner().{adapter}(|__value| {value})")
+}
+
+/// Joins the transpiled members, fields that hold functions are called as `(receiver.field)(args)`
+///
+/// Member calls are resolved for the type of their receiver, so functions of different types can share a name
+fn join_members(
+    ctx: &Context,
+    scope: &mut Scope,
+    receiver: Option<(&str, Option<TypeElement>)>,
+    elements: &[SingleExpression],
+) -> String {
+    let mut chain = receiver.as_ref().map(|(ident, _)| ident.to_string());
+    for (i, element) in elements.iter().enumerate() {
+        let member_call = match (&chain, element) {
+            (Some(_), SingleExpression::FunctionCall(call)) => {
+                let receiver_type = match &receiver {
+                    Some((_, ty)) => ty
+                        .clone()
+                        .and_then(|ty| infer_member_access(scope, ty, &elements[..i])),
+                    None => elements[0]
+                        .infer_type(scope)
+                        .and_then(|ty| infer_member_access(scope, ty, &elements[1..i])),
+                };
+                receiver_type.map(|ty| (call, ty))
+            }
+            _ => None,
+        };
+
+        chain = Some(match (chain, member_call) {
+            (Some(chain), Some((call, ty)))
+                if field_function(scope, &ty, &call.identifier).is_some() =>
+            {
+                transpile!(
+                    ctx,
+                    scope,
+                    "({chain}.{})({})",
+                    call.identifier,
+                    call.arguments
+                )
+            }
+            (Some(chain), Some((call, ty))) => {
+                let call = transpile_member_call(ctx, scope, call, Some(&ty));
+                format!("{chain}.{call}")
+            }
+            (Some(chain), None) => match element {
+                SingleExpression::FunctionCall(call) => {
+                    let call = transpile_member_call(ctx, scope, call, None);
+                    format!("{chain}.{call}")
+                }
+                _ => format!("{chain}.{}", element.transpile(ctx, scope)),
+            },
+            (None, _) => element.transpile(ctx, scope),
+        });
+    }
+    chain.unwrap_or_default()
+}
+
+/// Transpiles a member call on a receiver of the given type, member functions are resolved by name if the type is not known
+///
+/// Calls that match no member function are passed to Rust as method calls e.g.: `"42".parse()`,
+/// free functions with the same name are never called on a receiver
+fn transpile_member_call(
+    ctx: &Context,
+    scope: &mut Scope,
+    call: &FunctionCall,
+    receiver: Option<&TypeElement>,
+) -> String {
+    let receiver_type = receiver.cloned().unwrap_or_else(infer);
+    let labels = call.labels();
+    let func = ctx
+        .lookup
+        .resolve_function(Some(&receiver_type), &call.identifier, &labels);
+    if func.is_some() {
+        return transpile_call(ctx, scope, call, func);
+    }
+
+    if BORROWED_ITERATOR_FNS.contains(&call.identifier.as_str()) {
+        return call.transpile(ctx, scope);
+    }
+    if !labels.is_empty() {
+        todo!(
+            "TRANSPILER ERROR: No member function `{}` with the labels {} found",
+            call.identifier,
+            labels.join(", ")
+        )
+    }
+    let is_async = receiver.is_none() && is_async_member(ctx, call);
+    let await_suffix = await_suffix(scope, call, is_async);
+    transpile!(
+        ctx,
+        scope,
+        "{}({}){await_suffix}",
+        call.identifier,
+        call.arguments
+    )
+}
+
+/// Checks if a member call whose receiver type is not known is async, which requires all member functions with its name to agree
+fn is_async_member(ctx: &Context, call: &FunctionCall) -> bool {
+    let asyncness = ctx
+        .lookup
+        .functions
+        .values()
+        .filter(|func| {
+            func.signature.identifier == call.identifier && func.signature.receiver().is_some()
+        })
+        .map(|func| func.signature.asyncness.is_async())
+        .unique()
+        .collect_vec();
+    match asyncness.as_slice() {
+        [is_async] => *is_async,
+        [] => false,
+        _ => todo!(
+            "TRANSPILER ERROR: Only some member functions named `{}` are async, so the type of the receiver must be known to call it",
+            call.identifier
+        ),
+    }
+}
+
+fn safe_call(element: &SingleExpression) -> Option<&SingleExpression> {
+    match element {
+        SingleExpression::Postfix(postfix) => match postfix.as_ref() {
+            PostfixExpression::SafeCallExpression(safe_call) => Some(&safe_call.0),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl Transpile for ConstructorCall {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        let members = match ctx
+            .lookup
+            .resolve_type(&self.identifier)
+            .map(|decl| &decl.item)
+        {
+            Some(TypeDecl::Struct(decl)) => decl.members.as_slice(),
+            _ => &[],
+        };
+        let arguments = self
+            .arguments
             .iter()
-            .map(|r| transpile!(ctx, scope, "{}", r))
-            .collect_vec()
-            .join(".")
+            .map(|arg| {
+                let member = members.iter().find(|member| member.ident == arg.ident);
+                match member.map(|member| &member.r#type) {
+                    Some(ty) if is_union_value(ty, &arg.expression) => {
+                        let value = transpile_typed_value(ctx, scope, ty, &arg.expression);
+                        transpile!(ctx, scope, "{}: {value}", arg.ident)
+                    }
+                    Some(TypeElement::Function(function)) => {
+                        let value = transpile_function_value(
+                            ctx,
+                            scope,
+                            &arg.expression,
+                            function,
+                            &arg.ident,
+                        );
+                        transpile!(ctx, scope, "{}: {value}", arg.ident)
+                    }
+                    _ => arg.transpile(ctx, scope),
+                }
+            })
+            .join(", ");
+        transpile!(ctx, scope, "{} {{ {arguments} }}", self.identifier)
     }
 }
 
-impl_transpile!(ConstructorCall, "{} {{ {} }}", identifier, arguments,);
-impl_transpile!(ConstructorCallArg, "{}: {}", ident, expression);
+impl Transpile for ConstructorCallArg {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        let expression = transpile_owned_expression(ctx, &self.expression, scope);
+        transpile!(ctx, scope, "{}: {expression}", self.ident)
+    }
+}
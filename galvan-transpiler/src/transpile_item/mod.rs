@@ -1,16 +1,27 @@
 mod assignment;
 mod closure;
 mod collection;
+mod const_decl;
+mod r#enum;
+mod exit;
 mod fn_decl;
 mod function_call;
 mod ident;
+mod loops;
+mod r#match;
 mod member;
 mod operator;
+mod postfix;
 mod statement;
 mod r#struct;
 mod task;
 mod test_decl;
 mod toplevel;
 mod r#type;
-mod postfix;
 mod visibility;
+
+pub(crate) use fn_decl::transpile_fn;
+pub(crate) use r#type::{
+    escaped_name, generic_args, generic_params, transpile_union_decls, where_clause,
+};
+pub(crate) use statement::{transpile_body, BodyResult};
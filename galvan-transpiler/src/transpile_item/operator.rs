@@ -1,11 +1,14 @@
 use crate::context::Context;
 use crate::macros::{impl_transpile_variants, transpile};
+use crate::transpile_item::function_call::transpile_value_argument;
+use crate::type_inference::InferType;
 use crate::Transpile;
 use galvan_ast::{
-    ArithmeticOperator, CollectionOperator, ComparisonOperator, InfixOperator, LogicalOperator,
-    OperatorTree, OperatorTreeNode, SimpleExpression,
+    ArithmeticOperator, BitwiseOperator, CollectionOperator, ComparisonOperator, Ident,
+    InfixOperator, LogicalOperator, OperatorTree, OperatorTreeNode, PrefixExpression,
+    PrefixOperator, SimpleExpression, SingleExpression,
 };
-use galvan_resolver::Scope;
+use galvan_resolver::{Lookup, Scope};
 
 impl Transpile for OperatorTree {
     fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
@@ -17,20 +20,66 @@ impl Transpile for OperatorTree {
 
         match operator {
             InfixOperator::Arithmetic(op) => transpile_arithmetic(ctx, scope, *op, left, right),
+            InfixOperator::Bitwise(op) => transpile_bitwise(ctx, scope, *op, left, right),
             InfixOperator::Collection(op) => {
                 transpile_collection_operation(ctx, scope, *op, left, right)
             }
             InfixOperator::Comparison(op) => transpile_comparison(ctx, scope, *op, left, right),
             InfixOperator::Logical(op) => transpile_logical(ctx, scope, *op, left, right),
+            InfixOperator::NullCoalescing(_) => transpile_null_coalescing(ctx, scope, left, right),
             InfixOperator::CustomInfix(op) => {
-                todo!("Implement custom operator support")
+                let Some(func) = ctx.lookup.resolve_infix_operator(op.as_str()) else {
+                    todo!("TRANSPILER ERROR: Unknown infix operator {}", op.as_str())
+                };
+                let ident = func.signature.identifier.transpile(ctx, scope);
+                let left = transpile_operand(ctx, scope, left);
+                let right = transpile_operand(ctx, scope, right);
+                format!("{ident}({left}, {right})")
             }
         }
     }
 }
 
 impl_transpile_variants!(OperatorTreeNode; Operation, SimpleExpression);
-impl_transpile_variants!(SimpleExpression; MemberChain, SingleExpression);
+impl_transpile_variants!(SimpleExpression; PrefixExpression, MemberChain, SingleExpression);
+
+impl Transpile for PrefixExpression {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        let Self { operator, operand } = self;
+        match operator {
+            PrefixOperator::Not => transpile!(ctx, scope, "!({})", operand),
+            PrefixOperator::Negate => transpile!(ctx, scope, "-({})", operand),
+            PrefixOperator::BitwiseNot => transpile!(ctx, scope, "!({})", operand),
+            PrefixOperator::CustomPrefix(op) => {
+                let Some(func) = ctx.lookup.resolve_prefix_operator(op.as_str()) else {
+                    todo!("TRANSPILER ERROR: Unknown prefix operator {}", op.as_str())
+                };
+                let ident = func.signature.identifier.transpile(ctx, scope);
+                let ty = operand.infer_type(scope);
+                let operand =
+                    transpile_value_argument(ctx, scope, &**operand, simple_ident(operand), ty);
+                format!("{ident}({operand})")
+            }
+        }
+    }
+}
+
+/// Operands of custom operators are passed like the arguments of a function call
+fn transpile_operand(ctx: &Context, scope: &mut Scope, operand: &OperatorTreeNode) -> String {
+    let ident = match operand {
+        OperatorTreeNode::SimpleExpression(expr) => simple_ident(expr),
+        OperatorTreeNode::Operation(_) => None,
+    };
+    let ty = operand.infer_type(scope);
+    transpile_value_argument(ctx, scope, operand, ident, ty)
+}
+
+fn simple_ident(expr: &SimpleExpression) -> Option<&Ident> {
+    match expr {
+        SimpleExpression::SingleExpression(SingleExpression::Ident(ident)) => Some(ident),
+        _ => None,
+    }
+}
 
 fn transpile_logical(
     ctx: &Context,
@@ -42,10 +91,37 @@ fn transpile_logical(
     match op {
         LogicalOperator::And => transpile!(ctx, scope, "{} && {}", left, right),
         LogicalOperator::Or => transpile!(ctx, scope, "{} || {}", left, right),
-        LogicalOperator::Xor => todo!("Correctly handle xor chains"),
+        // Xor binds weaker than any other operator in Galvan, but stronger than && and || in Rust
+        LogicalOperator::Xor => transpile!(ctx, scope, "({}) ^ ({})", left, right),
     }
 }
 
+fn transpile_null_coalescing(
+    ctx: &Context,
+    scope: &mut Scope,
+    left: &OperatorTreeNode,
+    right: &OperatorTreeNode,
+) -> String {
+    // The fallback has to be owned, as it replaces the owned inner value of the left operand
+    let fallback = match right {
+        OperatorTreeNode::SimpleExpression(SimpleExpression::SingleExpression(
+            SingleExpression::Ident(ident),
+        )) => transpile!(ctx, scope, "{}.to_owned()", ident),
+        OperatorTreeNode::SimpleExpression(SimpleExpression::MemberChain(chain))
+            if chain.is_field() =>
+        {
+            transpile!(ctx, scope, "{}.to_owned()", chain)
+        }
+        right => right.transpile(ctx, scope),
+    };
+    transpile!(
+        ctx,
+        scope,
+        "({}).__to_option().unwrap_or_else(|| {fallback})",
+        left
+    )
+}
+
 fn transpile_comparison(
     ctx: &Context,
     scope: &mut Scope,
@@ -93,7 +169,10 @@ fn transpile_collection_operation(
                 right
             )
         }
-        CollectionOperator::Remove => todo!("Implement remove operator"),
+        // The right operand is a collection of the removed elements, or of the removed keys for dictionaries
+        CollectionOperator::Remove => {
+            transpile!(ctx, scope, "({}).__remove(&({}))", left, right)
+        }
         CollectionOperator::Contains => {
             transpile!(ctx, scope, "({}).contains(&({}))", right, left)
         }
@@ -116,3 +195,19 @@ fn transpile_arithmetic(
         ArithmeticOperator::Power => transpile!(ctx, scope, "{}.pow({})", left, right),
     }
 }
+
+fn transpile_bitwise(
+    ctx: &Context,
+    scope: &mut Scope,
+    op: BitwiseOperator,
+    left: &OperatorTreeNode,
+    right: &OperatorTreeNode,
+) -> String {
+    match op {
+        BitwiseOperator::Or => transpile!(ctx, scope, "{} | {}", left, right),
+        BitwiseOperator::And => transpile!(ctx, scope, "{} & {}", left, right),
+        BitwiseOperator::Xor => transpile!(ctx, scope, "{} ^ {}", left, right),
+        BitwiseOperator::LeftShift => transpile!(ctx, scope, "{} << {}", left, right),
+        BitwiseOperator::RightShift => transpile!(ctx, scope, "{} >> {}", left, right),
+    }
+}
@@ -1,19 +1,71 @@
-use galvan_ast::{AccessExpression, YeetExpression};
+use galvan_ast::{
+    AccessExpression, Expression, Ownership, SafeCallExpression, SingleExpression, SliceExpression,
+    TypeElement, YeetExpression,
+};
 use galvan_resolver::Scope;
 
+use crate::transpile_item::statement::match_ident;
+use crate::type_inference::InferType;
 use crate::{context::Context, macros::transpile, Transpile};
 
 impl Transpile for AccessExpression {
     fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
-        // TODO: typecheck that base is a collection and the key types matches
-        transpile!(ctx, scope, "{}[{}]", self.base, self.index)
+        // TODO: typecheck that the key type matches
+        match self.base.infer_type(scope) {
+            // Dictionary lookups result in an optional, as the key might not be present
+            Some(TypeElement::Dictionary(_) | TypeElement::OrderedDictionary(_)) => {
+                let key = transpile_key(ctx, scope, &self.index);
+                transpile!(ctx, scope, "{}.get({key}).cloned()", self.base)
+            }
+            // Accessing an index outside of the array panics
+            Some(TypeElement::Array(_)) => {
+                transpile!(ctx, scope, "{}[({}) as usize]", self.base, self.index)
+            }
+            _ => transpile!(ctx, scope, "{}[{}]", self.base, self.index),
+        }
+    }
+}
+
+/// Dictionaries are accessed by a reference to the key, variables that are borrowed already are one
+pub(crate) fn transpile_key(ctx: &Context, scope: &mut Scope, key: &Expression) -> String {
+    let borrowed = match key {
+        match_ident!(ident) => scope.get_variable(ident).is_some_and(|variable| {
+            matches!(
+                variable.ownership,
+                Ownership::Borrowed | Ownership::MutBorrowed
+            )
+        }),
+        _ => false,
+    };
+    if borrowed {
+        key.transpile(ctx, scope)
+    } else {
+        transpile!(ctx, scope, "&({})", key)
+    }
+}
+
+impl Transpile for SliceExpression {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        let mut bound = |bound: &Option<Expression>| match bound {
+            Some(bound) => transpile!(ctx, scope, "Some(({}) as i64)", bound),
+            None => "None".into(),
+        };
+        let start = bound(&self.start);
+        let end = bound(&self.end);
+        transpile!(ctx, scope, "({}).__slice({start}, {end})", self.base)
     }
 }
 
 impl Transpile for YeetExpression {
     fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
-        // TODO: check that type is error or optional 
+        // TODO: check that type is error or optional
         // TODO: check that we are inside a function that returns a compatible error
         transpile!(ctx, scope, "{}?", self.0)
     }
 }
+
+impl Transpile for SafeCallExpression {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        transpile!(ctx, scope, "({}).__borrow_inner()", self.0)
+    }
+}
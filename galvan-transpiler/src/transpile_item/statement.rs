@@ -1,35 +1,78 @@
 use crate::context::Context;
 use crate::macros::{impl_transpile_variants, transpile};
+use crate::transpile_item::closure::transpile_function_value;
+use crate::transpile_item::exit::transpile_return_value;
+use crate::transpile_item::r#struct::is_cloneable;
+use crate::transpile_item::r#type::{is_union_value, transpile_typed_value, transpile_union_value};
 use crate::type_inference::InferType;
 use crate::{Body, Transpile};
 use galvan_ast::{
-    BooleanLiteral, DeclModifier, Declaration, Expression, Literal, NumberLiteral, Ownership, PostfixExpression, SingleExpression, Statement, StringLiteral, TopExpression, TypeElement
+    BooleanLiteral, DeclModifier, Declaration, Expression, Group, Interpolation, Literal,
+    NumberLiteral, Ownership, PostfixExpression, SingleExpression, Statement, StringLiteral,
+    StringSegment, TopExpression, TypeElement, TypeIdent,
 };
-use galvan_resolver::{Scope, Variable};
+use galvan_resolver::{Lookup, Scope, Variable};
 use itertools::Itertools;
 
 impl Transpile for Body {
     fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
-        let mut body_scope = Scope::child(scope);
-        let scope = &mut body_scope;
+        transpile_body(ctx, scope, self, BodyResult::Value)
+    }
+}
 
-        let last = match self.statements.last() {
-            Some(Statement::Declaration(_)) | Some(Statement::Assignment(_)) => ";",
-            _ => "",
-        };
+/// Determines how the value of the last statement in a body is used
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BodyResult {
+    /// The body evaluates to the value of its last statement
+    Value,
+    /// The body of a function, its value is copied and converted to the return type of the function
+    Owned,
+    /// The value of the last statement is discarded, e.g. in the body of a loop
+    Discarded,
+}
 
-        format!(
-            "{{\n{}\n}}",
-            self.statements
-                .iter()
-                .map(|stmt| stmt.transpile(ctx, scope))
-                .join(";\n")
-                + last
-        )
-    }
+/// Transpiles a body, `result` determines how the value of its last statement is used
+pub(crate) fn transpile_body(
+    ctx: &Context,
+    scope: &mut Scope,
+    body: &Body,
+    result: BodyResult,
+) -> String {
+    let mut body_scope = Scope::child(scope);
+    let scope = &mut body_scope;
+
+    let last = match body.statements.last() {
+        Some(Statement::Declaration(_)) | Some(Statement::Assignment(_)) => ";",
+        Some(_) if result == BodyResult::Discarded => ";",
+        _ => "",
+    };
+
+    let statements = body
+        .statements
+        .iter()
+        .enumerate()
+        .map(|(i, stmt)| match stmt {
+            Statement::TopExpression(TopExpression::Expression(expr))
+                if result == BodyResult::Owned && i == body.statements.len() - 1 =>
+            {
+                transpile_return_value(ctx, scope, expr)
+            }
+            stmt => stmt.transpile(ctx, scope),
+        })
+        .join(";\n");
+    format!("{{\n{}\n}}", statements + last)
 }
 
-impl_transpile_variants!(Statement; Assignment, TopExpression, Declaration, Block);
+impl_transpile_variants! { Statement;
+    Assignment,
+    TopExpression,
+    Declaration,
+    ForLoop,
+    WhileLoop,
+    Break,
+    Continue,
+    Block
+}
 
 impl Transpile for Declaration {
     fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
@@ -44,6 +87,7 @@ impl Transpile for Declaration {
             self.assignment
                 .as_ref()
                 .and_then(|expr| expr.infer_type(scope))
+                .map(|ty| with_inferred_type_arguments(ctx, ty))
         });
 
         let ty = inferred_type
@@ -81,7 +125,19 @@ impl Transpile for Declaration {
         // TODO: Clone inner type from ref types to non-ref types
         self.assignment
             .as_ref()
-            .map(|expr| transpile_assignment_expression(ctx, expr, scope))
+            .map(|expr| match (&self.type_annotation, expr) {
+                (Some(TypeElement::Function(function)), TopExpression::Expression(expr)) => {
+                    transpile_function_value(ctx, scope, expr, function, &self.identifier)
+                }
+                (Some(ty), TopExpression::Expression(expr)) if is_union_value(ty, expr) => {
+                    transpile_typed_value(ctx, scope, ty, expr)
+                }
+                (Some(TypeElement::Union(union)), expr) => {
+                    let value = transpile_assignment_expression(ctx, expr, scope);
+                    transpile_union_value(ctx, union, &value)
+                }
+                (_, expr) => transpile_assignment_expression(ctx, expr, scope),
+            })
             .map(|expr| {
                 if matches!(self.decl_modifier, DeclModifier::Ref(_)) {
                     format!("(&({expr})).__to_ref()")
@@ -94,6 +150,39 @@ impl Transpile for Declaration {
     }
 }
 
+/// Type arguments of generic types are left to Rust when they were not specified
+fn with_inferred_type_arguments(ctx: &Context, ty: TypeElement) -> TypeElement {
+    let infer = |ty| with_inferred_type_arguments(ctx, ty);
+    match ty {
+        TypeElement::Plain(mut plain) => {
+            if plain.generics.is_empty() {
+                let params = ctx
+                    .lookup
+                    .resolve_type(&plain.ident)
+                    .map_or(0, |decl| decl.generics().len());
+                plain.generics = vec![TypeElement::plain(TypeIdent::new("__Infer")); params];
+            } else {
+                plain.generics = plain.generics.into_iter().map(infer).collect();
+            }
+            TypeElement::Plain(plain)
+        }
+        TypeElement::Array(array) => TypeElement::array(infer(array.elements)),
+        TypeElement::Set(set) => TypeElement::set(infer(set.elements)),
+        TypeElement::Dictionary(dict) => TypeElement::dict(infer(dict.key), infer(dict.value)),
+        TypeElement::OrderedDictionary(dict) => {
+            TypeElement::ordered_dict(infer(dict.key), infer(dict.value))
+        }
+        TypeElement::Tuple(tuple) => {
+            TypeElement::tuple(tuple.elements.into_iter().map(infer).collect())
+        }
+        TypeElement::Optional(optional) => TypeElement::optional(infer(optional.some)),
+        TypeElement::Result(result) => {
+            TypeElement::result(infer(result.success), result.error.map(infer))
+        }
+        ty @ (TypeElement::Generic(_) | TypeElement::Union(_) | TypeElement::Function(_)) => ty,
+    }
+}
+
 macro_rules! match_ident {
     ($p:pat) => {
         Expression::SingleExpression(SingleExpression::Ident($p))
@@ -107,21 +196,53 @@ fn transpile_assignment_expression(
     scope: &mut Scope,
 ) -> String {
     match assigned {
-        TopExpression::Expression(expr) => match expr {
-            match_ident!(ident) => {
-                transpile!(ctx, scope, "{}.to_owned()", ident)
-            }
-            Expression::MemberChain(access) if access.is_field() => {
-                transpile!(ctx, scope, "{}.to_owned()", access)
-            }
-            expr => expr.transpile(ctx, scope),
-        },
+        TopExpression::Expression(expr) => transpile_owned_expression(ctx, expr, scope),
         TopExpression::ElseExpression(e) => e.transpile(ctx, scope),
     }
 }
 
+/// Transpiles an expression whose value is stored somewhere else, so variables and fields are copied
+pub(crate) fn transpile_owned_expression(
+    ctx: &Context,
+    expr: &Expression,
+    scope: &mut Scope,
+) -> String {
+    match expr {
+        // Values that cannot be cloned are moved instead
+        match_ident!(ident)
+            if scope.get_variable(ident).is_some_and(|variable| {
+                variable.ownership == Ownership::Owned
+                    && variable
+                        .ty
+                        .as_ref()
+                        .is_some_and(|ty| !is_cloneable(ctx, ty))
+            }) =>
+        {
+            ident.transpile(ctx, scope)
+        }
+        match_ident!(ident) => {
+            transpile!(ctx, scope, "{}.to_owned()", ident)
+        }
+        Expression::MemberChain(access) if access.is_field() => {
+            transpile!(ctx, scope, "{}.to_owned()", access)
+        }
+        Expression::SingleExpression(SingleExpression::Postfix(postfix))
+            if matches!(postfix.as_ref(), PostfixExpression::AccessExpression(_)) =>
+        {
+            transpile!(ctx, scope, "{}.to_owned()", postfix)
+        }
+        expr => expr.transpile(ctx, scope),
+    }
+}
+
 impl_transpile_variants! { Expression;
+    MatchExpression,
+    LoopExpression,
+    Return,
+    Throw,
+    RangeExpression,
     OperatorTree,
+    PrefixExpression,
     MemberChain,
     SingleExpression,
     Closure
@@ -132,13 +253,24 @@ impl_transpile_variants! { SingleExpression;
     CollectionLiteral,
     FunctionCall,
     ConstructorCall,
+    EnumConstructor,
     Literal,
+    Group,
+    Block,
     Ident
 }
 
+impl Transpile for Group {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        transpile!(ctx, scope, "({})", self.0)
+    }
+}
+
 impl_transpile_variants! { PostfixExpression;
     YeetExpression,
+    SafeCallExpression,
     AccessExpression,
+    SliceExpression,
 }
 
 impl_transpile_variants! { Literal;
@@ -148,16 +280,60 @@ impl_transpile_variants! { Literal;
 }
 
 impl Transpile for StringLiteral {
-    fn transpile(&self, _: &Context, _scope: &mut Scope) -> String {
-        // TODO: Implement more sophisticated formatting (extract {} and put them as separate arguments)
-        format!("format!({})", self.as_str())
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        let mut format_string = String::new();
+        let mut arguments = String::new();
+        for segment in &self.segments {
+            match segment {
+                StringSegment::Text(text) => {
+                    format_string.push_str(&text.replace('{', "{{").replace('}', "}}"))
+                }
+                StringSegment::Interpolation(interpolation) => {
+                    check_interpolation(scope, interpolation);
+                    match &interpolation.format_spec {
+                        Some(spec) => format_string.push_str(&format!("{{:{spec}}}")),
+                        None => format_string.push_str("{}"),
+                    }
+                    let value = match &interpolation.expression {
+                        match_ident!(ident)
+                            if scope
+                                .get_variable(ident)
+                                .is_some_and(|var| var.ownership == Ownership::Ref) =>
+                        {
+                            transpile!(ctx, scope, "{}.lock().unwrap()", ident)
+                        }
+                        value => value.transpile(ctx, scope),
+                    };
+                    arguments.push_str(&format!(", {value}"));
+                }
+            }
+        }
+        format!("format!(\"{format_string}\"{arguments})")
+    }
+}
+
+/// Values are formatted with `Display`, which compound types only implement in the debug format e.g.: `{list:?}`
+fn check_interpolation(scope: &Scope, interpolation: &Interpolation) {
+    let is_debug = interpolation
+        .format_spec
+        .as_ref()
+        .is_some_and(|spec| spec.ends_with('?'));
+    match interpolation.expression.infer_type(scope) {
+        None | Some(TypeElement::Plain(_) | TypeElement::Generic(_) | TypeElement::Union(_)) => {}
+        Some(TypeElement::Function(_)) => {
+            todo!("TRANSPILER ERROR: Functions cannot be interpolated into strings")
+        }
+        Some(_) if is_debug => {}
+        Some(_) => todo!(
+            "TRANSPILER ERROR: Collections, optionals, results and tuples can only be interpolated in the debug format e.g.: `{{value:?}}`"
+        ),
     }
 }
 
 impl Transpile for NumberLiteral {
     fn transpile(&self, _: &Context, _scope: &mut Scope) -> String {
         // TODO: Parse number and validate type
-        format!("{}", self.as_str())
+        self.as_str().to_string()
     }
 }
 
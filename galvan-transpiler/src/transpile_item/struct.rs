@@ -1,18 +1,93 @@
 use crate::context::Context;
 use crate::macros::{impl_transpile, impl_transpile_match, transpile};
+use crate::transpile_item::r#type::{check_where_clause, generic_params, where_clause};
 use crate::{StructTypeMember, Transpile, TupleTypeMember, TypeDecl};
-use galvan_ast::DeclModifier;
-use galvan_resolver::Scope;
+use galvan_ast::{AliasTypeDecl, DeclModifier, StructTypeDecl, TupleTypeDecl, TypeElement};
+use galvan_resolver::{Lookup, Scope};
+use itertools::Itertools;
 
-static DERIVE: &str = "#[derive(Clone, Debug, PartialEq)]";
+pub(crate) static DERIVE: &str = "#[derive(Clone, Debug, PartialEq)]";
 
 impl_transpile_match! { TypeDecl,
-    Tuple(def) => ("{DERIVE} {} struct {}({});", def.visibility, def.ident, def.members),
-    Struct(def) => ("{DERIVE} {} struct {} {{\n{}\n}}", def.visibility, def.ident, def.members),
-    Alias(def) => ("{} type {} = {};", def.visibility, def.ident, def.r#type),
+    Enum(def) => ("{}", def),
+    Tuple(def) => ("{}", def),
+    Struct(def) => ("{}", def),
+    Alias(def) => ("{}", def),
     Empty(def) => ("{DERIVE} {} struct {};", def.visibility, def.ident),
 }
 
+impl Transpile for TupleTypeDecl {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        check_where_clause(self.where_clause.as_ref(), &self.generics(), &self.ident);
+        let where_clause = where_clause(ctx, &self.generics(), &self.where_clause);
+        let generics = generic_params(&self.generics());
+        transpile!(
+            ctx,
+            scope,
+            "{} {} struct {}{generics}({}){where_clause};",
+            derive(self.members.iter().map(|member| &member.r#type)),
+            self.visibility,
+            self.ident,
+            self.members
+        )
+    }
+}
+
+impl Transpile for StructTypeDecl {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        check_where_clause(self.where_clause.as_ref(), &self.generics(), &self.ident);
+        let where_clause = where_clause(ctx, &self.generics(), &self.where_clause);
+        let generics = generic_params(&self.generics());
+        transpile!(
+            ctx,
+            scope,
+            "{} {} struct {}{generics}{where_clause} {{\n{}\n}}",
+            derive(self.members.iter().map(|member| &member.r#type)),
+            self.visibility,
+            self.ident,
+            self.members
+        )
+    }
+}
+
+impl Transpile for AliasTypeDecl {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        check_where_clause(self.where_clause.as_ref(), &self.generics(), &self.ident);
+        let where_clause = where_clause(ctx, &self.generics(), &self.where_clause);
+        let generics = generic_params(&self.generics());
+        transpile!(
+            ctx,
+            scope,
+            "{} type {}{generics}{where_clause} = {};",
+            self.visibility,
+            self.ident,
+            self.r#type
+        )
+    }
+}
+
+/// Boxed functions cannot be cloned, compared or printed, so types that store them derive nothing
+fn derive<'a>(mut members: impl Iterator<Item = &'a TypeElement>) -> &'static str {
+    if members.any(|ty| matches!(ty, TypeElement::Function(_))) {
+        ""
+    } else {
+        DERIVE
+    }
+}
+
+/// Returns false for declared types that store functions, as they do not derive `Clone`
+pub(crate) fn is_cloneable(ctx: &Context, ty: &TypeElement) -> bool {
+    let TypeElement::Plain(plain) = ty else {
+        return true;
+    };
+    let members = match ctx.lookup.resolve_type(&plain.ident).map(|decl| &decl.item) {
+        Some(TypeDecl::Tuple(def)) => def.members.iter().map(|m| &m.r#type).collect_vec(),
+        Some(TypeDecl::Struct(def)) => def.members.iter().map(|m| &m.r#type).collect_vec(),
+        _ => return true,
+    };
+    !derive(members.into_iter()).is_empty()
+}
+
 impl_transpile!(TupleTypeMember, "{}", r#type);
 
 impl Transpile for StructTypeMember {
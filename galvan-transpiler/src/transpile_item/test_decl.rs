@@ -1,5 +1,5 @@
 use crate::context::Context;
-use crate::macros::transpile;
+use crate::transpile_entry_body;
 use crate::Transpile;
 use galvan_ast::TestDecl;
 use galvan_resolver::Scope;
@@ -9,12 +9,7 @@ impl Transpile for &(Cow<'_, str>, &TestDecl) {
     fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
         let (name, test_decl) = self;
         let name = name.as_ref();
-        transpile!(
-            ctx,
-            scope,
-            "#[test]\nfn {}() {{\n{}\n}}",
-            name,
-            test_decl.body
-        )
+        let body = transpile_entry_body(ctx, scope, test_decl.asyncness, &test_decl.body);
+        format!("#[test]\nfn {name}() {{\n{body}\n}}")
     }
 }
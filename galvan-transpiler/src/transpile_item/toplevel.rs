@@ -9,4 +9,4 @@ impl Transpile for Ast {
     }
 }
 
-impl_transpile_variants!(RootItem; Type, Fn, Main, Test);
+impl_transpile_variants!(RootItem; Type, Fn, Const, Main, Test);
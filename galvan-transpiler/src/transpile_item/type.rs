@@ -1,8 +1,14 @@
 use crate::context::Context;
 use crate::macros::{impl_transpile, impl_transpile_fn, impl_transpile_variants, transpile};
+use crate::transpile_item::r#struct::DERIVE;
+use crate::transpile_item::statement::transpile_owned_expression;
 use crate::{Transpile, TypeElement};
+use convert_case::{Case, Casing};
 use galvan_ast::*;
 use galvan_resolver::Scope;
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::iter;
 
 // TODO: Re-export used types from galvan library to avoid referencing the used crates directly
 
@@ -13,11 +19,98 @@ impl_transpile!(
     key,
     value
 );
-impl_transpile!(OrderedDictionaryTypeItem, "TODO {} {}", key, value);
+impl_transpile!(
+    OrderedDictionaryTypeItem,
+    "::galvan::std::IndexMap<{}, {}>",
+    key,
+    value
+);
 impl_transpile!(SetTypeItem, "::std::collections::HashSet<{}>", elements);
 impl_transpile!(TupleTypeItem, "({})", elements);
 impl_transpile_fn!(OptionalTypeItem, "Option<{}>", element);
-impl_transpile!(BasicTypeItem, "{}", ident);
+
+impl Transpile for BasicTypeItem {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        if self.generics.is_empty() {
+            transpile!(ctx, scope, "{}", self.ident)
+        } else {
+            transpile!(ctx, scope, "{}<{}>", self.ident, self.generics)
+        }
+    }
+}
+
+impl Transpile for GenericTypeItem {
+    fn transpile(&self, _ctx: &Context, _scope: &mut Scope) -> String {
+        generic_name(&self.ident)
+    }
+}
+
+/// Type parameters are lowercase in Galvan but follow the Rust naming convention in the transpiled code
+pub(crate) fn generic_name(ident: &TypeIdent) -> String {
+    ident.as_str().to_case(Case::UpperCamel)
+}
+
+/// Transpiles a list of type parameters to a Rust generic parameter list e.g.: `<T, U>`
+pub(crate) fn generic_params(generics: &[&TypeIdent]) -> String {
+    generic_args(generics)
+}
+
+/// Transpiles a list of type parameters to Rust generic arguments e.g.: `<T, U>`
+pub(crate) fn generic_args(generics: &[&TypeIdent]) -> String {
+    if generics.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", generics.iter().map(|g| generic_name(g)).join(", "))
+    }
+}
+
+/// Transpiles the bounds of the given type parameters to a Rust where clause e.g.: ` where T: ::std::hash::Hash`
+///
+/// Bounds on type parameters that are not in `generics` are skipped, as they are declared elsewhere
+pub(crate) fn where_clause<'a>(
+    ctx: &Context,
+    generics: &[&TypeIdent],
+    clauses: impl IntoIterator<Item = &'a WhereClause>,
+) -> String {
+    let clauses = clauses.into_iter().collect_vec();
+    let bounds = generics
+        .iter()
+        .filter_map(|generic| {
+            let traits = clauses
+                .iter()
+                .flat_map(|clause| clause.bounds_of(generic))
+                .unique()
+                .map(|t| ctx.mapping.get_trait(t))
+                .join(" + ");
+            (!traits.is_empty()).then(|| format!("{}: {traits}", generic_name(generic)))
+        })
+        .join(", ");
+
+    if bounds.is_empty() {
+        String::new()
+    } else {
+        format!(" where {bounds}")
+    }
+}
+
+/// Ensures that a where clause only constrains type parameters that are declared by `name`
+pub(crate) fn check_where_clause(
+    clause: Option<&WhereClause>,
+    generics: &[&TypeIdent],
+    name: &dyn std::fmt::Display,
+) {
+    let Some(clause) = clause else { return };
+    if let Some(bound) = clause
+        .bounds
+        .iter()
+        .find(|bound| !generics.contains(&&bound.generic.ident))
+    {
+        todo!(
+            "TRANSPILER ERROR: Type parameter {} in where clause is not used by {name}",
+            bound.generic.ident
+        )
+    }
+}
 
 impl Transpile for ResultTypeItem {
     fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
@@ -39,4 +132,322 @@ impl_transpile_variants! { TypeElement;
     Tuple
     Optional
     Result
+    Generic
+    Union
+    Function
+}
+
+/// Function types are boxed when they are stored, function parameters use `impl Fn` instead
+impl Transpile for FunctionTypeItem {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        format!(
+            "::std::boxed::Box<dyn {}>",
+            function_bound(ctx, scope, self, "Fn")
+        )
+    }
+}
+
+/// Transpiles a function type to a Rust closure trait e.g.: `FnMut(i64, &str) -> bool`
+pub(crate) fn function_bound(
+    ctx: &Context,
+    scope: &mut Scope,
+    function: &FunctionTypeItem,
+    closure_trait: &str,
+) -> String {
+    let params = function.parameters.transpile(ctx, scope);
+    match &function.return_type {
+        Some(return_type) => transpile!(ctx, scope, "{closure_trait}({params}) -> {}", return_type),
+        None => format!("{closure_trait}({params})"),
+    }
+}
+
+/// Parameters of function types are passed like the parameters of functions
+impl Transpile for FunctionTypeParam {
+    fn transpile(&self, ctx: &Context, scope: &mut Scope) -> String {
+        use crate::transpile_item::ident::{TranspileType, TypeOwnership};
+        match (self.decl_modifier, &self.param_type) {
+            (Some(DeclModifier::Let(_)) | None, TypeElement::Plain(plain)) => {
+                let ty = plain
+                    .ident
+                    .transpile_type(ctx, scope, TypeOwnership::Borrowed);
+                if ctx.mapping.is_copy(&plain.ident) {
+                    ty
+                } else {
+                    format!("&{ty}")
+                }
+            }
+            (Some(DeclModifier::Let(_)) | None, ty) => transpile!(ctx, scope, "&{}", ty),
+            (Some(DeclModifier::Mut(_)), TypeElement::Plain(plain)) => format!(
+                "&mut {}",
+                plain
+                    .ident
+                    .transpile_type(ctx, scope, TypeOwnership::MutBorrowed)
+            ),
+            (Some(DeclModifier::Mut(_)), ty) => transpile!(ctx, scope, "&mut {}", ty),
+            (Some(DeclModifier::Ref(_)), ty) => {
+                transpile!(ctx, scope, "std::sync::Arc<std::sync::Mutex<{}>>", ty)
+            }
+        }
+    }
+}
+
+impl Transpile for UnionTypeItem {
+    fn transpile(&self, ctx: &Context, _scope: &mut Scope) -> String {
+        let name = union_name(ctx, self);
+        format!("{name}{}", generic_args(&union_generics(self)))
+    }
+}
+
+/// Name of the anonymous enum that holds the value of a union type e.g.: `__Union_Int_String`
+///
+/// The union is registered in the context, so its enum is generated alongside the other items
+pub(crate) fn union_name(ctx: &Context, union: &UnionTypeItem) -> String {
+    let name = format!(
+        "__{}",
+        escaped_name(&TypeElement::Union(union.clone().into()))
+    );
+    ctx.unions
+        .borrow_mut()
+        .entry(name.clone())
+        .or_insert_with(|| union.clone());
+    name
+}
+
+/// Type parameters of a union are sorted like its members, so equal unions share the same enum
+fn union_generics(union: &UnionTypeItem) -> Vec<&TypeIdent> {
+    union
+        .generics()
+        .into_iter()
+        .sorted_by_key(|generic| generic.as_str())
+        .collect()
+}
+
+/// Converts a value into the union type, this is a no-op for values that already have the union type
+pub(crate) fn transpile_union_value(ctx: &Context, union: &UnionTypeItem, value: &str) -> String {
+    format!("{}::from({value})", union_name(ctx, union))
+}
+
+/// Checks if a value is transpiled with `transpile_typed_value` to be stored as the given type
+pub(crate) fn is_union_value(ty: &TypeElement, value: &Expression) -> bool {
+    match value {
+        Expression::SingleExpression(SingleExpression::CollectionLiteral(_)) => contains_union(ty),
+        _ => matches!(ty, TypeElement::Union(_)),
+    }
+}
+
+/// Checks if values of the type are wrapped into the enum of a union, also as elements of a collection
+fn contains_union(ty: &TypeElement) -> bool {
+    match ty {
+        TypeElement::Union(_) => true,
+        TypeElement::Array(array) => contains_union(&array.elements),
+        TypeElement::Set(set) => contains_union(&set.elements),
+        TypeElement::Dictionary(dict) => contains_union(&dict.key) || contains_union(&dict.value),
+        TypeElement::OrderedDictionary(dict) => {
+            contains_union(&dict.key) || contains_union(&dict.value)
+        }
+        _ => false,
+    }
+}
+
+/// Transpiles a value that is stored as the given type, so values of union types are wrapped into the enum of the union
+///
+/// The elements of collection literals are wrapped as well e.g.: `[1, "one"]` as `[Int | String]`
+pub(crate) fn transpile_typed_value(
+    ctx: &Context,
+    scope: &mut Scope,
+    ty: &TypeElement,
+    value: &Expression,
+) -> String {
+    let literal = match value {
+        Expression::SingleExpression(SingleExpression::CollectionLiteral(literal))
+            if contains_union(ty) =>
+        {
+            Some(literal)
+        }
+        _ => None,
+    };
+    match (ty, literal) {
+        (TypeElement::Union(union), _) => {
+            let value = transpile_owned_expression(ctx, value, scope);
+            transpile_union_value(ctx, union, &value)
+        }
+        (TypeElement::Array(array), Some(CollectionLiteral::ArrayLiteral(literal))) => {
+            let elements = typed_elements(ctx, scope, &array.elements, &literal.elements);
+            format!("vec![{elements}]")
+        }
+        (TypeElement::Set(set), Some(CollectionLiteral::SetLiteral(literal))) => {
+            let elements = typed_elements(ctx, scope, &set.elements, &literal.elements);
+            format!("::std::collections::HashSet::from([{elements}])")
+        }
+        (TypeElement::Dictionary(dict), Some(CollectionLiteral::DictLiteral(literal))) => {
+            let entries = typed_entries(ctx, scope, (&dict.key, &dict.value), &literal.elements);
+            format!("::std::collections::HashMap::from([{entries}])")
+        }
+        (
+            TypeElement::OrderedDictionary(dict),
+            Some(CollectionLiteral::OrderedDictLiteral(literal)),
+        ) => {
+            let entries = typed_entries(ctx, scope, (&dict.key, &dict.value), &literal.elements);
+            format!("::galvan::std::IndexMap::from([{entries}])")
+        }
+        _ => transpile_owned_expression(ctx, value, scope),
+    }
+}
+
+fn typed_elements(
+    ctx: &Context,
+    scope: &mut Scope,
+    ty: &TypeElement,
+    elements: &[Expression],
+) -> String {
+    elements
+        .iter()
+        .map(|element| transpile_typed_value(ctx, scope, ty, element))
+        .join(", ")
+}
+
+fn typed_entries(
+    ctx: &Context,
+    scope: &mut Scope,
+    (key_type, value_type): (&TypeElement, &TypeElement),
+    elements: &[DictLiteralElement],
+) -> String {
+    elements
+        .iter()
+        .map(|element| {
+            let key = transpile_typed_value(ctx, scope, key_type, &element.key);
+            let value = transpile_typed_value(ctx, scope, value_type, &element.value);
+            format!("({key}, {value})")
+        })
+        .join(", ")
+}
+
+/// Transpiles the enums for all union types that were used in the transpiled code
+pub(crate) fn transpile_union_decls(ctx: &Context, scope: &mut Scope) -> String {
+    let mut decls = BTreeMap::new();
+    // Transpiling a union can register further unions that are nested in its members
+    loop {
+        let pending = ctx
+            .unions
+            .borrow()
+            .iter()
+            .filter(|(name, _)| !decls.contains_key(*name))
+            .map(|(name, union)| (name.clone(), union.clone()))
+            .collect_vec();
+        if pending.is_empty() {
+            break;
+        }
+        for (name, union) in pending {
+            let decl = transpile_union_decl(ctx, scope, &name, &union);
+            decls.insert(name, decl);
+        }
+    }
+    decls.into_values().join("\n\n")
+}
+
+fn transpile_union_decl(
+    ctx: &Context,
+    scope: &mut Scope,
+    name: &str,
+    union: &UnionTypeItem,
+) -> String {
+    let generics = union_generics(union);
+    let generic_params = generic_params(&generics);
+    let generic_args = generic_args(&generics);
+    let members = union
+        .elements
+        .iter()
+        .map(|ty| (escaped_name(ty), ty))
+        .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        .dedup_by(|(a, _), (b, _)| a == b)
+        .map(|(variant, ty)| (variant, ty, ty.transpile(ctx, scope)))
+        .collect_vec();
+
+    let variants = members
+        .iter()
+        .map(|(variant, _, rust_type)| format!("{variant}({rust_type})"))
+        .join(",\n");
+    // Conversions from type parameters could overlap with the other members
+    let conversions = members
+        .iter()
+        .filter(|(_, ty, _)| ty.generics().is_empty())
+        .unique_by(|(_, _, rust_type)| rust_type)
+        .map(|(variant, _, rust_type)| {
+            format!(
+                "impl{generic_params} From<{rust_type}> for {name}{generic_args} {{\n\
+                fn from(value: {rust_type}) -> Self {{\nSelf::{variant}(value)\n}}\n}}"
+            )
+        })
+        .join("\n\n");
+
+    // Builtin types can all be displayed, so their unions can be displayed as well
+    let display = if union.elements.iter().all(
+        |ty| matches!(ty, TypeElement::Plain(plain) if ctx.mapping.types.contains_key(&plain.ident)),
+    ) {
+        let arms = members
+            .iter()
+            .map(|(variant, _, _)| format!("Self::{variant}(value) => value.fmt(f)"))
+            .join(",\n");
+        format!(
+            "\n\nimpl ::std::fmt::Display for {name} {{\n\
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {{\n\
+            match self {{\n{arms}\n}}\n}}\n}}"
+        )
+    } else {
+        String::new()
+    };
+
+    format!("{DERIVE} pub enum {name}{generic_params} {{\n{variants}\n}}\n\n{conversions}{display}")
+}
+
+/// Name of a type that can be used as part of a Rust identifier e.g.: `Array_Int`
+pub(crate) fn escaped_name(ty: &TypeElement) -> String {
+    match ty {
+        TypeElement::Plain(ty) => iter::once(ty.ident.as_str().to_case(Case::UpperCamel))
+            .chain(ty.generics.iter().map(escaped_name))
+            .join("_"),
+        TypeElement::Generic(ty) => ty.ident.as_str().to_case(Case::UpperCamel),
+        TypeElement::Tuple(ty) => format!(
+            "Tuple_{}",
+            ty.elements
+                .iter()
+                .map(escaped_name)
+                .collect::<Vec<_>>()
+                .join("_")
+        ),
+        TypeElement::Result(ty) => format!(
+            "Result_{}_{}",
+            escaped_name(&ty.success),
+            ty.error.as_ref().map_or("".into(), escaped_name)
+        ),
+        TypeElement::Optional(ty) => format!("Option_{}_Ext", escaped_name(&ty.some)),
+        TypeElement::Dictionary(ty) => {
+            format!("Dict_{}_{}", escaped_name(&ty.key), escaped_name(&ty.value))
+        }
+        TypeElement::OrderedDictionary(ty) => format!(
+            "OrderedDict_{}_{}",
+            escaped_name(&ty.key),
+            escaped_name(&ty.value)
+        ),
+        TypeElement::Array(ty) => format!("Array_{}", escaped_name(&ty.elements)),
+        TypeElement::Set(ty) => format!("Set_{}", escaped_name(&ty.elements)),
+        TypeElement::Function(ty) => format!(
+            "Fn_{}_{}",
+            ty.parameters
+                .iter()
+                .map(|param| escaped_name(&param.param_type))
+                .join("_"),
+            ty.return_type.as_ref().map_or("".into(), escaped_name)
+        ),
+        // Members are sorted, so the same union is always transpiled to the same enum
+        TypeElement::Union(ty) => format!(
+            "Union_{}",
+            ty.elements
+                .iter()
+                .map(escaped_name)
+                .sorted()
+                .dedup()
+                .join("_")
+        ),
+    }
 }
@@ -1,11 +1,14 @@
 use galvan_ast::{
-    ArrayLiteral, ArrayTypeItem, BasicTypeItem, Block, Body, CollectionLiteral, CollectionOperator,
-    DictLiteral, DictLiteralElement, DictionaryTypeItem, ElseExpression, Expression, InfixOperator,
-    Literal, MemberChain, OperatorTree, OperatorTreeNode, OrderedDictLiteral,
-    OrderedDictionaryTypeItem, SetLiteral, SetTypeItem, SimpleExpression, SingleExpression,
+    ArrayLiteral, ArrayTypeItem, BasicTypeItem, BitwiseOperator, Block, Body, Break,
+    CollectionLiteral, CollectionOperator, DeclModifier, DictLiteral, DictLiteralElement,
+    DictionaryTypeItem, ElseExpression, EnumPattern, EnumVariantFields, Expression, FnDecl,
+    FunctionCall, FunctionTypeItem, Ident, InfixOperator, Label, LetKeyword, Literal,
+    LoopExpression, MatchExpression, MemberChain, OperatorTree, OperatorTreeNode,
+    OrderedDictLiteral, OrderedDictionaryTypeItem, Ownership, Pattern, PostfixExpression,
+    PrefixExpression, PrefixOperator, SetLiteral, SetTypeItem, SimpleExpression, SingleExpression,
     Statement, TopExpression, TypeDecl, TypeElement, TypeIdent,
 };
-use galvan_resolver::{Lookup, Scope};
+use galvan_resolver::{Lookup, Scope, Variable};
 use itertools::Itertools;
 
 pub(crate) trait InferType {
@@ -23,17 +26,29 @@ impl InferType for TopExpression {
 
 impl InferType for ElseExpression {
     fn infer_type(&self, scope: &Scope) -> Option<TypeElement> {
-        let receiver_type = self.receiver.infer_type(scope);
+        // The else block is only evaluated if the receiver has no value, so only the inner type is relevant
+        let receiver_type = self.receiver.infer_type(scope).map(|ty| match ty {
+            TypeElement::Optional(optional) => optional.some,
+            TypeElement::Result(result) => result.success,
+            ty => ty,
+        });
         let block_type = self.block.infer_type(scope);
 
+        // An `else if` chain without a final else branch only has a value if one of the conditions is true
+        if self.is_open() {
+            let block_type = block_type.map(|ty| match ty {
+                TypeElement::Optional(optional) => optional.some,
+                ty => ty,
+            });
+            let value_type = match (receiver_type, block_type) {
+                (Some(receiver_type), Some(block_type)) => Some(unify(receiver_type, block_type)),
+                (receiver_type, block_type) => receiver_type.or(block_type),
+            };
+            return value_type.map(TypeElement::optional);
+        }
+
         match (receiver_type, block_type) {
-            (Some(receiver_type), Some(block_type)) => {
-                if receiver_type == block_type {
-                    Some(receiver_type)
-                } else {
-                    todo!("TRANSPILER ERROR: Types of if and else expression don't match. (allow this when type unions are implemented)")
-                }
-            }
+            (Some(receiver_type), Some(block_type)) => Some(unify(receiver_type, block_type)),
             (Some(receiver_type), None) => Some(receiver_type),
             (None, Some(block_type)) => Some(block_type),
             (None, None) => None,
@@ -41,6 +56,263 @@ impl InferType for ElseExpression {
     }
 }
 
+/// Combines the types of two branches, branches of different types result in a union of both types
+fn unify(left: TypeElement, right: TypeElement) -> TypeElement {
+    if left == right {
+        return left;
+    }
+
+    // Number literals take the numeric type of the other branch
+    match (is_number_literal(&left), is_number_literal(&right)) {
+        (true, false) if is_number(&right) => right,
+        (false, true) if is_number(&left) => left,
+        _ => TypeElement::union(with_default_number(left), with_default_number(right)),
+    }
+}
+
+fn is_number_literal(ty: &TypeElement) -> bool {
+    matches!(ty, TypeElement::Plain(plain) if plain.ident.as_str() == "__Number")
+}
+
+fn is_number(ty: &TypeElement) -> bool {
+    const NUMBERS: [&str; 16] = [
+        "I8", "I16", "I32", "I64", "I128", "Int", "ISize", "U8", "U16", "U32", "U64", "U128",
+        "UInt", "USize", "Float", "Double",
+    ];
+    matches!(ty, TypeElement::Plain(plain) if NUMBERS.contains(&plain.ident.as_str()))
+}
+
+/// Converts an inferred type into a type that can be written in a declaration
+///
+/// Number literals default to `Int`, types that contain other placeholders cannot be declared
+pub(crate) fn declarable_type(ty: TypeElement) -> Option<TypeElement> {
+    fn is_placeholder(ty: &TypeElement) -> bool {
+        match ty {
+            TypeElement::Plain(plain) => {
+                plain.ident.as_str().starts_with("__") || plain.generics.iter().any(is_placeholder)
+            }
+            TypeElement::Array(array) => is_placeholder(&array.elements),
+            TypeElement::Set(set) => is_placeholder(&set.elements),
+            TypeElement::Dictionary(dict) => {
+                is_placeholder(&dict.key) || is_placeholder(&dict.value)
+            }
+            TypeElement::OrderedDictionary(dict) => {
+                is_placeholder(&dict.key) || is_placeholder(&dict.value)
+            }
+            TypeElement::Tuple(tuple) => tuple.elements.iter().any(is_placeholder),
+            TypeElement::Union(union) => union.elements.iter().any(is_placeholder),
+            TypeElement::Optional(optional) => is_placeholder(&optional.some),
+            TypeElement::Result(result) => {
+                is_placeholder(&result.success) || result.error.as_ref().is_some_and(is_placeholder)
+            }
+            TypeElement::Generic(_) => false,
+            TypeElement::Function(function) => {
+                function
+                    .parameters
+                    .iter()
+                    .any(|param| is_placeholder(&param.param_type))
+                    || function.return_type.as_ref().is_some_and(is_placeholder)
+            }
+        }
+    }
+
+    let ty = with_default_number(ty);
+    (!is_placeholder(&ty)).then_some(ty)
+}
+
+/// Rust cannot infer the type of a number literal in a union, so it defaults to `Int`
+fn with_default_number(ty: TypeElement) -> TypeElement {
+    if is_number_literal(&ty) {
+        TypeElement::plain(TypeIdent::new("Int"))
+    } else {
+        ty
+    }
+}
+
+impl InferType for MatchExpression {
+    fn infer_type(&self, scope: &Scope) -> Option<TypeElement> {
+        let subject_type = self.subject.infer_type(scope);
+        self.arms.iter().find_map(|arm| {
+            let mut arm_scope = Scope::child(scope);
+            declare_pattern_bindings(&mut arm_scope, &arm.pattern, subject_type.as_ref());
+            arm.body.infer_type(&arm_scope)
+        })
+    }
+}
+
+/// Declares the variables bound by a pattern with the types of the values they are bound to
+fn declare_pattern_bindings(scope: &mut Scope, pattern: &Pattern, ty: Option<&TypeElement>) {
+    match pattern {
+        Pattern::Binding(ident) => scope.declare_variable(Variable {
+            ident: ident.clone(),
+            modifier: DeclModifier::Let(LetKeyword),
+            ty: ty.cloned(),
+            ownership: Ownership::Owned,
+        }),
+        Pattern::Tuple(elements) => {
+            for (i, element) in elements.iter().enumerate() {
+                let element_type = match ty {
+                    Some(TypeElement::Tuple(tuple)) if tuple.elements.len() == elements.len() => {
+                        tuple.elements.get(i)
+                    }
+                    _ => None,
+                };
+                declare_pattern_bindings(scope, element, element_type);
+            }
+        }
+        Pattern::Some(inner) => {
+            let inner_type = match ty {
+                Some(TypeElement::Optional(optional)) => Some(&optional.some),
+                _ => None,
+            };
+            declare_pattern_bindings(scope, inner, inner_type);
+        }
+        Pattern::Enum(pattern) => {
+            let field_types = enum_field_types(scope, pattern);
+            for (field, field_type) in pattern.fields.iter().zip(field_types) {
+                declare_pattern_bindings(scope, &field.pattern, field_type.as_ref());
+            }
+        }
+        Pattern::Wildcard | Pattern::Literal(_) | Pattern::None => {}
+    }
+}
+
+/// The types of the fields matched by an enum pattern, shared fields are matched by name
+fn enum_field_types(scope: &Scope, pattern: &EnumPattern) -> Vec<Option<TypeElement>> {
+    let Some(TypeDecl::Enum(decl)) = scope
+        .resolve_type(&pattern.enum_ident)
+        .map(|decl| &decl.item)
+    else {
+        return vec![None; pattern.fields.len()];
+    };
+    let variant = decl.variant(&pattern.variant);
+    let mut position = 0;
+    pattern
+        .fields
+        .iter()
+        .map(|field| {
+            let shared = field
+                .ident
+                .as_ref()
+                .and_then(|ident| decl.fields.iter().find(|f| f.ident == *ident));
+            if let Some(shared) = shared {
+                return Some(shared.r#type.clone());
+            }
+            // Unnamed fields of the variant are matched in declaration order
+            let index = position;
+            position += 1;
+            match (&variant?.fields, &field.ident) {
+                (EnumVariantFields::Struct(members), Some(name)) => members
+                    .iter()
+                    .find(|m| m.ident == *name)
+                    .map(|m| m.r#type.clone()),
+                (EnumVariantFields::Struct(members), None) => {
+                    members.get(index).map(|m| m.r#type.clone())
+                }
+                (EnumVariantFields::Tuple(members), _) => {
+                    members.get(index).map(|m| m.r#type.clone())
+                }
+                (EnumVariantFields::Unit, _) => None,
+            }
+        })
+        .collect()
+}
+
+impl InferType for LoopExpression {
+    fn infer_type(&self, scope: &Scope) -> Option<TypeElement> {
+        // The loop has the value that is passed to `break`
+        let mut values = vec![];
+        collect_break_values(&self.body.body, self.label.as_ref(), false, &mut values);
+        values
+            .into_iter()
+            .filter_map(|value| value.infer_type(scope))
+            .reduce(unify)
+    }
+}
+
+/// Collects the values of all `break` statements in the body that exit the loop with the given label
+///
+/// Inside of nested loops, only a `break` that names the label exits the outer loop
+fn collect_break_values<'a>(
+    body: &'a Body,
+    label: Option<&Label>,
+    nested: bool,
+    values: &mut Vec<&'a Expression>,
+) {
+    for statement in &body.statements {
+        match statement {
+            Statement::Break(Break {
+                label: target,
+                value: Some(value),
+            }) => {
+                let exits_loop = match target {
+                    Some(target) => label == Some(target),
+                    None => !nested,
+                };
+                if exits_loop {
+                    values.push(value);
+                }
+            }
+            Statement::ForLoop(inner) => {
+                collect_break_values(&inner.body.body, label, true, values)
+            }
+            Statement::WhileLoop(inner) => {
+                collect_break_values(&inner.body.body, label, true, values)
+            }
+            Statement::Block(block) => collect_break_values(&block.body, label, nested, values),
+            Statement::TopExpression(TopExpression::Expression(expr)) => {
+                collect_break_values_in_expression(expr, label, nested, values)
+            }
+            Statement::TopExpression(TopExpression::ElseExpression(expr)) => {
+                if let SingleExpression::FunctionCall(call) = expr.receiver.as_ref() {
+                    collect_break_values_in_call(call, label, nested, values);
+                }
+                collect_break_values(&expr.block.body, label, nested, values);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn collect_break_values_in_expression<'a>(
+    expr: &'a Expression,
+    label: Option<&Label>,
+    nested: bool,
+    values: &mut Vec<&'a Expression>,
+) {
+    match expr {
+        Expression::LoopExpression(inner) => {
+            collect_break_values(&inner.body.body, label, true, values)
+        }
+        Expression::MatchExpression(expr) => {
+            for arm in &expr.arms {
+                collect_break_values(&arm.body.body, label, nested, values);
+            }
+        }
+        Expression::SingleExpression(SingleExpression::FunctionCall(call)) => {
+            collect_break_values_in_call(call, label, nested, values)
+        }
+        Expression::SingleExpression(SingleExpression::Block(block)) => {
+            collect_break_values(&block.body, label, nested, values)
+        }
+        _ => {}
+    }
+}
+
+/// Bodies of control flow functions like `if` are passed as trailing closures
+fn collect_break_values_in_call<'a>(
+    call: &'a FunctionCall,
+    label: Option<&Label>,
+    nested: bool,
+    values: &mut Vec<&'a Expression>,
+) {
+    for arg in &call.arguments {
+        if let Expression::Closure(closure) = &arg.expression {
+            collect_break_values(&closure.block.body, label, nested, values);
+        }
+    }
+}
+
 impl InferType for Block {
     fn infer_type(&self, scope: &Scope) -> Option<TypeElement> {
         // TODO: Block should have access to its inner scope
@@ -62,6 +334,8 @@ impl InferType for Statement {
             Statement::Assignment(_) => None,
             Statement::TopExpression(expr) => expr.infer_type(scope),
             Statement::Declaration(_) => None,
+            Statement::ForLoop(_) | Statement::WhileLoop(_) => None,
+            Statement::Break(_) | Statement::Continue(_) => None,
             Statement::Block(block) => block.infer_type(scope),
         }
     }
@@ -74,40 +348,160 @@ impl InferType for Expression {
                 // todo!("Implement type inference for closure")
                 None
             }
+            Expression::MatchExpression(expr) => expr.infer_type(scope),
+            Expression::LoopExpression(expr) => expr.infer_type(scope),
+            // Exiting the function does not produce a value
+            Expression::Return(_) | Expression::Throw(_) => None,
+            // TODO: Add a range type to the standard library
+            Expression::RangeExpression(_) => None,
             Expression::OperatorTree(tree) => tree.infer_type(scope),
+            Expression::PrefixExpression(prefix) => prefix.infer_type(scope),
             Expression::MemberChain(access) => access.infer_type(scope),
             Expression::SingleExpression(s) => s.infer_type(scope),
         }
     }
 }
 
+/// Type of the elements that are produced when iterating over the given expression
+pub(crate) fn infer_element_type(iterable: &Expression, scope: &Scope) -> Option<TypeElement> {
+    if let Expression::RangeExpression(range) = iterable {
+        return operand_type(range.start.infer_type(scope), range.end.infer_type(scope));
+    }
+
+    match iterable.infer_type(scope)? {
+        TypeElement::Array(array) => Some(array.elements),
+        TypeElement::Set(set) => Some(set.elements),
+        TypeElement::Dictionary(dict) => Some(TypeElement::tuple(vec![dict.key, dict.value])),
+        TypeElement::OrderedDictionary(dict) => {
+            Some(TypeElement::tuple(vec![dict.key, dict.value]))
+        }
+        _ => None,
+    }
+}
+
 impl InferType for SimpleExpression {
     fn infer_type(&self, scope: &Scope) -> Option<TypeElement> {
         match self {
+            SimpleExpression::PrefixExpression(prefix) => prefix.infer_type(scope),
             SimpleExpression::MemberChain(access) => access.infer_type(scope),
             SimpleExpression::SingleExpression(expr) => expr.infer_type(scope),
         }
     }
 }
 
+impl InferType for PrefixExpression {
+    fn infer_type(&self, scope: &Scope) -> Option<TypeElement> {
+        match self.operator {
+            PrefixOperator::Not => Some(bool()),
+            PrefixOperator::Negate | PrefixOperator::BitwiseNot => self.operand.infer_type(scope),
+            PrefixOperator::CustomPrefix(ref op) => {
+                let func = scope.resolve_prefix_operator(op.as_str())?;
+                function_return_type(scope, func)
+            }
+        }
+    }
+}
+
 impl InferType for SingleExpression {
     fn infer_type(&self, scope: &Scope) -> Option<TypeElement> {
         match self {
             SingleExpression::CollectionLiteral(collection) => collection.infer_type(scope),
-            SingleExpression::FunctionCall(call) => {
-                // todo!("Implement type inference for function call")
-                None
-            }
+            SingleExpression::FunctionCall(call) => call.infer_type(scope),
             SingleExpression::ConstructorCall(constructor) => {
                 Some(constructor.identifier.clone().into())
             }
+            SingleExpression::EnumConstructor(constructor) => {
+                Some(constructor.enum_ident.clone().into())
+            }
             SingleExpression::Literal(literal) => literal.infer_type(scope),
+            SingleExpression::Group(group) => group.0.infer_type(scope),
+            SingleExpression::Block(block) => block.infer_type(scope),
             SingleExpression::Ident(ident) => scope.get_variable(ident)?.ty.clone()?.into(),
-            SingleExpression::Postfix(_) => todo!(),
+            SingleExpression::Postfix(postfix) => postfix.infer_type(scope),
         }
     }
 }
 
+impl InferType for PostfixExpression {
+    fn infer_type(&self, scope: &Scope) -> Option<TypeElement> {
+        match self {
+            PostfixExpression::YeetExpression(yeet) => match yeet.0.infer_type(scope)? {
+                TypeElement::Optional(optional) => Some(optional.some),
+                TypeElement::Result(result) => Some(result.success),
+                _ => None,
+            },
+            // The receiver of a safe call is unwrapped by the member chain
+            PostfixExpression::SafeCallExpression(safe_call) => safe_call.0.infer_type(scope),
+            PostfixExpression::AccessExpression(access) => match access.base.infer_type(scope)? {
+                TypeElement::Array(array) => Some(array.elements),
+                TypeElement::Dictionary(dict) => Some(TypeElement::optional(dict.value)),
+                TypeElement::OrderedDictionary(dict) => Some(TypeElement::optional(dict.value)),
+                _ => None,
+            },
+            // Slices of arrays and strings have the same type as the sliced value
+            PostfixExpression::SliceExpression(slice) => match slice.base.infer_type(scope)? {
+                ty @ TypeElement::Array(_) => Some(ty),
+                TypeElement::Plain(plain) if plain.ident.as_str() == "String" => {
+                    Some(TypeElement::Plain(plain))
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
+impl InferType for FunctionCall {
+    fn infer_type(&self, scope: &Scope) -> Option<TypeElement> {
+        match self.identifier.as_str() {
+            // `if` and `try` have the value of their body if the condition is true or the optional has a value
+            "if" | "try" => match self.arguments.last().map(|arg| &arg.expression) {
+                Some(Expression::Closure(closure)) => {
+                    closure.block.infer_type(scope).map(TypeElement::optional)
+                }
+                _ => None,
+            },
+            // Variables that hold functions return the return type of their function type
+            _ => match scope.get_variable(&self.identifier) {
+                Some(variable) => match variable.ty.as_ref()? {
+                    TypeElement::Function(function) => function.return_type.clone(),
+                    _ => None,
+                },
+                None => {
+                    let func = scope.resolve_function(None, &self.identifier, &self.labels())?;
+                    // Type parameters of the function are not known at the call site
+                    function_return_type(scope, func).filter(|ty| ty.generics().is_empty())
+                }
+            },
+        }
+    }
+}
+
+/// The declared return type of a function or the type of its body if it is an expression
+pub(crate) fn function_return_type(scope: &Scope, func: &FnDecl) -> Option<TypeElement> {
+    let signature = &func.signature;
+    if !func.is_expression_body || signature.return_type.is_some() {
+        return signature.return_type.clone();
+    }
+
+    // The function is declared as a variable without type, so recursive calls are not inferred again
+    let mut scope = Scope::child(scope);
+    scope.declare_variable(Variable {
+        ident: signature.identifier.clone(),
+        modifier: DeclModifier::Let(LetKeyword),
+        ty: None,
+        ownership: Ownership::Owned,
+    });
+    for param in &signature.parameters.params {
+        scope.declare_variable(Variable {
+            ident: param.identifier.clone(),
+            modifier: param.decl_modifier.unwrap_or(DeclModifier::Let(LetKeyword)),
+            ty: Some(param.param_type.clone()),
+            ownership: Ownership::Owned,
+        });
+    }
+    func.block.infer_type(&scope).and_then(declarable_type)
+}
+
 impl InferType for Literal {
     fn infer_type(&self, _scope: &Scope) -> Option<TypeElement> {
         match self {
@@ -115,12 +509,14 @@ impl InferType for Literal {
             Literal::StringLiteral(_) => Some(
                 BasicTypeItem {
                     ident: TypeIdent::new("String"),
+                    generics: vec![],
                 }
                 .into(),
             ),
             Literal::NumberLiteral(_) => Some(
                 BasicTypeItem {
                     ident: TypeIdent::new("__Number"),
+                    generics: vec![],
                 }
                 .into(),
             ),
@@ -137,36 +533,63 @@ impl InferType for OperatorTree {
         } = self;
 
         match operator {
-            InfixOperator::Arithmetic(op) => {
-                // todo!("Implement type inference for arithmetic operator")
-                None
+            // Both operands of an arithmetic operation have the same type
+            InfixOperator::Arithmetic(_) => {
+                operand_type(left.infer_type(scope), right.infer_type(scope))
+            }
+            // Shifting keeps the type of the shifted value, the shift amount can be any integer
+            InfixOperator::Bitwise(BitwiseOperator::LeftShift | BitwiseOperator::RightShift) => {
+                left.infer_type(scope)
+            }
+            InfixOperator::Bitwise(_) => {
+                operand_type(left.infer_type(scope), right.infer_type(scope))
             }
             InfixOperator::Collection(op) => infer_collection_operation(scope, *op, left, right),
             InfixOperator::Comparison(_) => Some(bool()),
             InfixOperator::Logical(_) => Some(bool()),
+            // The fallback is only used if the left operand has no value
+            InfixOperator::NullCoalescing(_) => {
+                let left = left.infer_type(scope).map(|ty| match ty {
+                    TypeElement::Optional(optional) => optional.some,
+                    TypeElement::Result(result) => result.success,
+                    ty => ty,
+                });
+                operand_type(left, right.infer_type(scope))
+            }
             InfixOperator::CustomInfix(op) => {
-                // todo!("Implement type inference for custom infix operator")
-                None
+                let func = scope.resolve_infix_operator(op.as_str())?;
+                function_return_type(scope, func)
             }
         }
     }
 }
 
+impl InferType for OperatorTreeNode {
+    fn infer_type(&self, scope: &Scope) -> Option<TypeElement> {
+        match self {
+            OperatorTreeNode::Operation(tree) => tree.infer_type(scope),
+            OperatorTreeNode::SimpleExpression(expr) => expr.infer_type(scope),
+        }
+    }
+}
+
+/// Type of two operands that have the same type, number literals take the numeric type of the other operand
+fn operand_type(left: Option<TypeElement>, right: Option<TypeElement>) -> Option<TypeElement> {
+    match (left, right) {
+        (Some(left), _) if !is_number_literal(&left) => Some(left),
+        (left, right) => right.or(left),
+    }
+}
+
 fn infer_collection_operation(
     scope: &Scope,
     op: CollectionOperator,
     lhs: &OperatorTreeNode,
-    rhs: &OperatorTreeNode,
+    _rhs: &OperatorTreeNode,
 ) -> Option<TypeElement> {
     match op {
-        CollectionOperator::Concat => {
-            // todo!("Implement type inference for collection concat")
-            None
-        }
-        CollectionOperator::Remove => {
-            // todo!("Implement type inference for collection remove")
-            None
-        }
+        // Elements are added to or removed from the left operand
+        CollectionOperator::Concat | CollectionOperator::Remove => lhs.infer_type(scope),
         CollectionOperator::Contains => Some(bool()),
     }
 }
@@ -174,68 +597,156 @@ fn infer_collection_operation(
 fn bool() -> TypeElement {
     BasicTypeItem {
         ident: TypeIdent::new("Bool"),
+        generics: vec![],
     }
     .into()
 }
 
-fn infer() -> TypeElement {
+pub(crate) fn infer() -> TypeElement {
     BasicTypeItem {
         ident: TypeIdent::new("__Infer"),
+        generics: vec![],
     }
     .into()
 }
 
 impl InferType for MemberChain {
     fn infer_type(&self, scope: &Scope) -> Option<TypeElement> {
-        let Self { elements } = self;
+        let (receiver, members) = self.elements.split_first()?;
+        infer_member_access(scope, receiver.infer_type(scope)?, members)
+    }
+}
 
-        let receiver_type = if elements.len() == 2 {
-            elements[0].infer_type(scope)?
-        } else {
-            return None;
-        };
+/// Type of accessing the given members on a receiver of the given type
+///
+/// Every member that follows a safe call `?.` wraps the resulting type in an optional
+pub(crate) fn infer_member_access(
+    scope: &Scope,
+    receiver_type: TypeElement,
+    members: &[SingleExpression],
+) -> Option<TypeElement> {
+    let mut ty = receiver_type;
+    let mut is_safe_call = false;
+    let mut is_optional = false;
 
-        match receiver_type {
-            TypeElement::Plain(ty) => {
-                let ty = &scope.resolve_type(&ty.ident)?.item;
+    for member in members {
+        if is_safe_call {
+            is_optional = true;
+            ty = match ty {
+                TypeElement::Optional(optional) => optional.some,
+                TypeElement::Result(result) => result.success,
+                _ => todo!("TRANSPILER ERROR: ?. can only be used on optionals and results"),
+            };
+        }
 
-                match ty {
-                    TypeDecl::Tuple(tuple) => {
-                        todo!("IMPLEMENT: Access member of tuple type")
-                    }
-                    TypeDecl::Struct(st) => {
-                        let field = self.field_ident()?;
-                        st.members
-                            .iter()
-                            .find(|member| member.ident == *field)
-                            .map(|member| member.r#type.clone())
-                    }
-                    TypeDecl::Alias(_) => {
-                        // TODO: Handle Inference for alias types
-                        None
-                    }
-                    TypeDecl::Empty(_) => {
-                        todo!("TRANSPILER ERROR: Cannot access member of empty type")
-                    }
+        let member = match member {
+            SingleExpression::Postfix(postfix) => match postfix.as_ref() {
+                PostfixExpression::SafeCallExpression(safe_call) => {
+                    is_safe_call = true;
+                    &safe_call.0
                 }
+                _ => return None,
+            },
+            member => {
+                is_safe_call = false;
+                member
             }
-            TypeElement::Optional(_) | TypeElement::Result(_) => {
-                // TODO: Handle inference for optional and result types
-                // TODO: Ultimately transition to a compiler error here
-                //  that tells the user to use safe-call ?. or forward-error-call !.
-                None
+        };
+        ty = infer_member_type(scope, ty, member)?;
+    }
+
+    match ty {
+        TypeElement::Optional(_) => Some(ty),
+        TypeElement::Result(result) if is_optional => Some(TypeElement::optional(result.success)),
+        ty if is_optional => Some(TypeElement::optional(ty)),
+        ty => Some(ty),
+    }
+}
+
+fn infer_member_type(
+    scope: &Scope,
+    receiver_type: TypeElement,
+    member: &SingleExpression,
+) -> Option<TypeElement> {
+    let field = match member {
+        SingleExpression::Ident(field) => field,
+        // Fields that hold functions can be called like member functions
+        SingleExpression::FunctionCall(call) => {
+            if let Some(function) = field_function(scope, &receiver_type, &call.identifier) {
+                return function.return_type;
             }
-            other => {
-                if self.is_field() {
-                    todo!(
-                        "TRANSPILER ERROR: Cannot access member of type {:#?}",
-                        other
-                    )
-                } else {
+            // Member functions are resolved for the type of their receiver
+            let func =
+                scope.resolve_function(Some(&receiver_type), &call.identifier, &call.labels())?;
+            // Type parameters of the function are not known at the call site
+            return func
+                .signature
+                .return_type
+                .clone()
+                .filter(|return_type| return_type.generics().is_empty());
+        }
+        _ => return None,
+    };
+
+    match receiver_type {
+        TypeElement::Plain(ty) => {
+            let ty = &scope.resolve_type(&ty.ident)?.item;
+
+            match ty {
+                TypeDecl::Enum(en) => en
+                    .fields
+                    .iter()
+                    .find(|member| member.ident == *field)
+                    .map(|member| member.r#type.clone()),
+                TypeDecl::Tuple(_tuple) => {
+                    todo!("IMPLEMENT: Access member of tuple type")
+                }
+                TypeDecl::Struct(st) => st
+                    .members
+                    .iter()
+                    .find(|member| member.ident == *field)
+                    .map(|member| member.r#type.clone()),
+                TypeDecl::Alias(_) => {
+                    // TODO: Handle Inference for alias types
                     None
                 }
+                TypeDecl::Empty(_) => {
+                    todo!("TRANSPILER ERROR: Cannot access member of empty type")
+                }
             }
         }
+        TypeElement::Optional(_) | TypeElement::Result(_) => {
+            // TODO: Ultimately transition to a compiler error here
+            //  that tells the user to use safe-call ?. or forward-error-call !.
+            None
+        }
+        other => todo!(
+            "TRANSPILER ERROR: Cannot access member of type {:#?}",
+            other
+        ),
+    }
+}
+
+/// Returns the function type of a struct field, if the receiver is a struct with a field of that name that holds a function
+pub(crate) fn field_function(
+    scope: &Scope,
+    receiver_type: &TypeElement,
+    field: &Ident,
+) -> Option<FunctionTypeItem> {
+    let TypeElement::Plain(ty) = receiver_type else {
+        return None;
+    };
+    let TypeDecl::Struct(decl) = &scope.resolve_type(&ty.ident)?.item else {
+        return None;
+    };
+    match &decl
+        .members
+        .iter()
+        .find(|member| member.ident == *field)?
+        .r#type
+    {
+        TypeElement::Function(function) => Some(function.as_ref().clone()),
+        _ => None,
     }
 }
 
@@ -1,5 +1,8 @@
 pub mod std;
 
+#[cfg(feature = "async")]
+pub mod runtime;
+
 pub use galvan_transpiler::galvan_module;
 
 #[cfg(feature = "build")]
@@ -0,0 +1,10 @@
+use std::future::Future;
+
+/// Runs the body of an async `main` or test to completion
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to start the async runtime")
+        .block_on(future)
+}
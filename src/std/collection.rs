@@ -0,0 +1,79 @@
+use indexmap::IndexMap;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Removes all elements that are contained in `removed`, dictionaries remove the contained keys instead
+///
+/// The collection itself is not modified, the remaining elements are returned as a new collection
+pub trait __Remove<R: ?Sized> {
+    fn __remove(&self, removed: &R) -> Self;
+}
+
+impl<T: Clone, R: __Contains<T> + ?Sized> __Remove<R> for Vec<T> {
+    fn __remove(&self, removed: &R) -> Self {
+        self.iter()
+            .filter(|element| !removed.__contains(element))
+            .cloned()
+            .collect()
+    }
+}
+
+impl<T: Clone + Eq + Hash, R: __Contains<T> + ?Sized> __Remove<R> for HashSet<T> {
+    fn __remove(&self, removed: &R) -> Self {
+        self.iter()
+            .filter(|element| !removed.__contains(element))
+            .cloned()
+            .collect()
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone, R: __Contains<K> + ?Sized> __Remove<R> for HashMap<K, V> {
+    fn __remove(&self, removed: &R) -> Self {
+        self.iter()
+            .filter(|(key, _)| !removed.__contains(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone, R: __Contains<K> + ?Sized> __Remove<R> for IndexMap<K, V> {
+    fn __remove(&self, removed: &R) -> Self {
+        self.iter()
+            .filter(|(key, _)| !removed.__contains(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+}
+
+/// Collections that can be used as the right operand of `--`
+pub trait __Contains<T> {
+    fn __contains(&self, element: &T) -> bool;
+}
+
+impl<T: PartialEq> __Contains<T> for [T] {
+    #[inline(always)]
+    fn __contains(&self, element: &T) -> bool {
+        self.contains(element)
+    }
+}
+
+impl<T: PartialEq> __Contains<T> for Vec<T> {
+    #[inline(always)]
+    fn __contains(&self, element: &T) -> bool {
+        self.as_slice().contains(element)
+    }
+}
+
+impl<T: Eq + Hash> __Contains<T> for HashSet<T> {
+    #[inline(always)]
+    fn __contains(&self, element: &T) -> bool {
+        self.contains(element)
+    }
+}
+
+impl<T, C: __Contains<T> + ?Sized> __Contains<T> for &C {
+    #[inline(always)]
+    fn __contains(&self, element: &T) -> bool {
+        (**self).__contains(element)
+    }
+}
@@ -14,11 +14,17 @@ where
 
 pub trait __ToOption<T: ?Sized> {
     type Inner: Borrow<T>;
+    /// The value that is bound by `else |error| { ... }`, optionals use `()` as they carry no error
+    type Error: ?Sized;
 
     fn __to_option(&self) -> Option<Self::Inner>;
 
     fn __borrow_inner(&self) -> Option<&T>;
 
+    fn __to_result(&self) -> Result<Self::Inner, &Self::Error>;
+
+    fn __borrow_result(&self) -> Result<&T, &Self::Error>;
+
     #[inline(always)]
     fn __or_else<F>(&self, f: F) -> Self::Inner
     where
@@ -30,6 +36,7 @@ pub trait __ToOption<T: ?Sized> {
 
 impl<T: ToOwned> __ToOption<T> for Option<T> {
     type Inner = T::Owned;
+    type Error = ();
 
     #[inline(always)]
     fn __to_option(&self) -> Option<Self::Inner> {
@@ -39,10 +46,20 @@ impl<T: ToOwned> __ToOption<T> for Option<T> {
     fn __borrow_inner(&self) -> Option<&T> {
         self.as_ref().map(|x| x.borrow())
     }
+
+    #[inline(always)]
+    fn __to_result(&self) -> Result<Self::Inner, &Self::Error> {
+        self.as_ref().map(|x| x.to_owned()).ok_or(&())
+    }
+
+    fn __borrow_result(&self) -> Result<&T, &Self::Error> {
+        self.as_ref().ok_or(&())
+    }
 }
 
 impl<T: ToOwned> __ToOption<T> for &Option<T> {
     type Inner = T::Owned;
+    type Error = ();
 
     #[inline(always)]
     fn __to_option(&self) -> Option<Self::Inner> {
@@ -52,10 +69,20 @@ impl<T: ToOwned> __ToOption<T> for &Option<T> {
     fn __borrow_inner(&self) -> Option<&T> {
         self.as_ref().map(|x| x.borrow())
     }
+
+    #[inline(always)]
+    fn __to_result(&self) -> Result<Self::Inner, &Self::Error> {
+        self.as_ref().map(|x| x.to_owned()).ok_or(&())
+    }
+
+    fn __borrow_result(&self) -> Result<&T, &Self::Error> {
+        self.as_ref().ok_or(&())
+    }
 }
 
 impl<T: ToOwned, _E> __ToOption<T> for Result<T, _E> {
     type Inner = T::Owned;
+    type Error = _E;
 
     #[inline(always)]
     fn __to_option(&self) -> Option<Self::Inner> {
@@ -65,6 +92,15 @@ impl<T: ToOwned, _E> __ToOption<T> for Result<T, _E> {
     fn __borrow_inner(&self) -> Option<&T> {
         self.as_ref().ok().map(|x| x.borrow())
     }
+
+    #[inline(always)]
+    fn __to_result(&self) -> Result<Self::Inner, &Self::Error> {
+        self.as_ref().map(|x| x.to_owned())
+    }
+
+    fn __borrow_result(&self) -> Result<&T, &Self::Error> {
+        self.as_ref()
+    }
 }
 
 #[inline(always)]
@@ -1,13 +1,20 @@
 mod borrow;
 pub use borrow::*;
 
+mod collection;
+pub use collection::*;
+
 mod control_flow;
 pub use control_flow::*;
 
 mod result;
 pub use result::*;
 
+mod slice;
+pub use slice::*;
+
 // External re-exports
+pub use indexmap::IndexMap;
 pub use itertools::*;
 pub trait ItertoolsExt: Itertools {
     fn vec(self) -> Vec<Self::Item>
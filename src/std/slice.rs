@@ -0,0 +1,41 @@
+use std::ops::Range;
+
+/// Slices a collection by element index, strings are sliced by characters instead of bytes
+///
+/// Bounds are clamped to the length of the collection, so slicing never panics
+pub trait __Slice {
+    type Output;
+
+    fn __slice(&self, start: Option<i64>, end: Option<i64>) -> Self::Output;
+}
+
+impl<T: Clone> __Slice for [T] {
+    type Output = Vec<T>;
+
+    #[inline(always)]
+    fn __slice(&self, start: Option<i64>, end: Option<i64>) -> Self::Output {
+        self[clamped_range(start, end, self.len())].to_vec()
+    }
+}
+
+impl __Slice for str {
+    type Output = String;
+
+    fn __slice(&self, start: Option<i64>, end: Option<i64>) -> Self::Output {
+        let range = clamped_range(start, end, self.chars().count());
+        // Character indices are converted to byte offsets, so the slice never splits a character
+        let offset = |index: usize| {
+            self.char_indices()
+                .nth(index)
+                .map_or(self.len(), |(offset, _)| offset)
+        };
+        self[offset(range.start)..offset(range.end)].to_owned()
+    }
+}
+
+fn clamped_range(start: Option<i64>, end: Option<i64>, len: usize) -> Range<usize> {
+    let clamp = |bound: i64| usize::try_from(bound).map_or(0, |bound| bound.min(len));
+    let end = end.map_or(len, clamp);
+    let start = start.map_or(0, clamp).min(end);
+    start..end
+}